@@ -1,17 +1,32 @@
+mod protocol;
+mod schema;
 mod table;
 mod transport;
 
+pub use protocol::*;
+pub use schema::{fingerprint, Schema};
 pub use table::*;
 pub use transport::*;
 
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
 
-use crate::state::{buildings::BuildingKind, recipes::CraftingRecipeKind};
+use crate::{
+    persistence::{LoadError, Replay, SavePayload, SlotId, SlotMetadata},
+    state::{buildings::BuildingKind, recipes::CraftingRecipeKind, resources::ResourceKind},
+};
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy)]
 pub enum TimeControl {
     Start,
     Pause,
+    /// Scales how fast simulation time passes relative to wall-clock time.
+    SetSpeed(f64),
+    /// Runs the given number of ticks while paused, sending out the changes after each one.
+    ///
+    /// Steps are not counted as play time.
+    Step(u32),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
@@ -20,25 +35,127 @@ pub enum WorkOrderKind {
     Construct(BuildingKind),
 }
 
-#[derive(Serialize, Deserialize, Debug, Clone, Copy)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum Intent {
-    /// Stub for initializing game session.
-    Load,
+    /// Tells the engine which protocol the client speaks, answered with
+    /// [`EngineMessage::Handshake`]. Nothing else is accepted from the client or sent to it
+    /// until its protocol is found compatible, when it is also sent a snapshot.
+    ///
+    /// Kept as the first variant so that it is encoded the same way by every build.
+    Handshake(ProtocolInfo),
+    /// Replaces the simulation state with the provided one.
+    ///
+    /// Answered with [`EngineMessage::Loaded`], or [`EngineMessage::LoadFailed`] if the payload
+    /// could not be decoded.
+    Load(SavePayload),
+    /// Requests the current simulation state, answered with [`EngineMessage::Saved`].
+    Save,
+    /// Requests the current simulation state as portable text, answered with
+    /// [`EngineMessage::Exported`].
+    Export,
+    /// Replaces the simulation state with one from portable text produced by [`Intent::Export`].
+    Import(String),
+    /// Requests everything that fed the simulation since the last load, answered with
+    /// [`EngineMessage::Replay`].
+    ExportReplay,
+    /// Requests a bundle of the state, recent activity and engine log for a bug report, answered
+    /// with [`EngineMessage::Diagnostics`].
+    ExportDiagnostics,
+    /// Requests the save slots, answered with [`EngineMessage::Slots`].
+    ListSlots,
+    /// Starts a new game in a new slot with the given name and opens it.
+    CreateSlot(String),
+    /// Loads the game in the slot and makes it the one autosaves are written to.
+    OpenSlot(SlotId),
+    RenameSlot(SlotId, String),
+    /// Copies the slot into a new slot with the given name.
+    DuplicateSlot(SlotId, String),
+    /// Deletes the slot, unless it is the open one that autosaves are written to.
+    DeleteSlot(SlotId),
     TimeControl(TimeControl),
+    /// Restores the world to how it was at the start of an earlier in-game day, where one day
+    /// back is the start of the current day.
+    Rewind {
+        days: u32,
+    },
     QueueWorkOrder(WorkOrderKind),
+    /// The game was hidden from view or shown again. Hidden games run fewer, longer frames.
+    PageHidden(bool),
+    /// Requests the full current state, answered with [`EngineMessage::Snapshot`].
+    Snapshot,
+    /// Marks the connection it is sent on as a read-only spectator, which receives updates but
+    /// can only send intents that leave the game as it is.
+    Spectate,
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum EngineMessage {
+    /// Kept as the first variant so that it is encoded the same way by every build.
+    Handshake(HandshakeReply),
     Loaded,
+    Saved(SavePayload),
+    Exported(String),
+    Replay(Replay),
+    /// A [`crate::diagnostics::DiagnosticBundle`] encoded as portable text.
+    Diagnostics(String),
+    LoadFailed(LoadError),
+    /// The save slots, sent on startup and whenever they change.
+    Slots(Vec<SlotMetadata>),
+    /// The slot was opened and its game loaded.
+    SlotOpened(SlotMetadata),
+    /// The simulation caught up on time that passed while it was suspended or saved.
+    OfflineProgress(OfflineSummary),
     Updated(Vec<EngineUpdate>),
+    /// Everything that [`EngineMessage::Updated`] reports on, for a client starting from scratch.
+    ///
+    /// Sent to each connection when it connects, and whenever it is requested.
+    Snapshot(Box<Snapshot>),
+}
+
+/// What changed while the simulation was fast-forwarded over time spent away.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct OfflineSummary {
+    pub elapsed_seconds: f64,
+    /// Change in the amount of each resource, leaving out resources that did not change.
+    pub resources: BTreeMap<ResourceKind, f64>,
+    pub days: u64,
+    pub seasons: u64,
+    pub years: u64,
+}
+
+/// Every transport with every value filled in.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct Snapshot {
+    pub buildings: BuildingTransport,
+    pub calendar: CalendarTransport,
+    pub fulfillments: FulfillmentTransport,
+    pub pacing: PacingTransport,
+    pub resources: ResourceTransport,
+    pub time: TimeTransport,
+    pub visibility: VisibilityTransport,
+}
+
+impl Snapshot {
+    /// The snapshot as updates, which bring a client in its initial state up to date.
+    pub fn into_updates(self) -> Vec<EngineUpdate> {
+        vec![
+            EngineUpdate::BuildingsChanged(self.buildings),
+            EngineUpdate::CalendarChanged(self.calendar),
+            EngineUpdate::FulfillmentsChanged(self.fulfillments),
+            EngineUpdate::PacingChanged(self.pacing),
+            EngineUpdate::ResourcesChanged(self.resources),
+            EngineUpdate::TimeChanged(self.time),
+            EngineUpdate::VisibilityChanged(self.visibility),
+        ]
+    }
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub enum EngineUpdate {
     CalendarChanged(CalendarTransport),
     BuildingsChanged(BuildingTransport),
     FulfillmentsChanged(FulfillmentTransport),
+    PacingChanged(PacingTransport),
     ResourcesChanged(ResourceTransport),
     TimeChanged(TimeTransport),
     VisibilityChanged(VisibilityTransport),
@@ -0,0 +1,47 @@
+use serde::{Deserialize, Serialize};
+
+use super::{fingerprint, EngineMessage, Intent, Schema};
+
+/// Version of the protocol between the UI and the engine.
+///
+/// Bump this when messages change meaning without their types changing, which the
+/// [schema hash](SCHEMA_HASH) cannot notice.
+pub const PROTOCOL_VERSION: u32 = 1;
+
+/// Hash of the [schema](Schema) of the messages in both directions.
+pub const SCHEMA_HASH: u64 = fingerprint(
+    "protocol",
+    &[Intent::FINGERPRINT, EngineMessage::FINGERPRINT],
+);
+
+/// What each side of a connection speaks, exchanged before anything else.
+///
+/// The layout of this type has to stay the same across versions, so that any two builds can read
+/// each other's.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProtocolInfo {
+    pub version: u32,
+    pub schema_hash: u64,
+}
+
+impl ProtocolInfo {
+    /// The protocol spoken by this build.
+    pub fn current() -> Self {
+        Self {
+            version: PROTOCOL_VERSION,
+            schema_hash: SCHEMA_HASH,
+        }
+    }
+
+    pub fn is_compatible_with(&self, other: &Self) -> bool {
+        self == other
+    }
+}
+
+/// The engine's answer to a handshake.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HandshakeReply {
+    pub engine: ProtocolInfo,
+    /// Whether the engine understands the client, which otherwise has to be reloaded.
+    pub compatible: bool,
+}
@@ -0,0 +1,476 @@
+//! A declared description of every type that is sent between the UI and the engine, which both
+//! sides compare through the [schema hash](super::SCHEMA_HASH) of their handshake.
+//!
+//! The description names the variants and fields of each type, in the order they are encoded, along
+//! with the types they hold. Documentation and code that does not change the encoding are left out,
+//! so only a change to how messages read tells two builds apart.
+
+use std::collections::BTreeMap;
+
+use crate::{
+    persistence::{LoadError, Replay, ReplayEntry, ReplayInput, SavePayload, SlotId, SlotMetadata},
+    state::{
+        buildings::BuildingKind,
+        calendar::SeasonKind,
+        recipes::{CraftingRecipeKind, FulfillmentState, RecipeKind},
+        resources::ResourceKind,
+        time::RunningState,
+        ui::{BonfireNodeId, NavigationNodeId, NodeId, ResourceNodeId},
+    },
+};
+
+use super::{
+    BuildingTransport, CalendarTransport, EngineMessage, EngineUpdate, FulfillmentTransport,
+    HandshakeReply, Intent, OfflineSummary, PacingTransport, ProtocolInfo, ResourceTransport,
+    Snapshot, StateTable, TimeControl, TimeTransport, VisibilityTransport, WorkOrderKind,
+};
+
+/// A type that is sent between the UI and the engine.
+pub trait Schema {
+    /// Hash of the description of the type, along with the fingerprints of the types it holds.
+    const FINGERPRINT: u64;
+}
+
+/// 64-bit FNV-1a hash of the description, followed by the fingerprints it is made of.
+pub const fn fingerprint(description: &str, parts: &[u64]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let mut hash = OFFSET_BASIS;
+    let bytes = description.as_bytes();
+    let mut index = 0;
+    while index < bytes.len() {
+        hash = (hash ^ bytes[index] as u64).wrapping_mul(PRIME);
+        index += 1;
+    }
+
+    let mut index = 0;
+    while index < parts.len() {
+        let part = parts[index].to_le_bytes();
+        let mut byte = 0;
+        while byte < part.len() {
+            hash = (hash ^ part[byte] as u64).wrapping_mul(PRIME);
+            byte += 1;
+        }
+        index += 1;
+    }
+    hash
+}
+
+macro_rules! primitives {
+    ($($type:ty),* $(,)?) => {$(
+        impl Schema for $type {
+            const FINGERPRINT: u64 = fingerprint(stringify!($type), &[]);
+        }
+    )*};
+}
+
+primitives![bool, i16, i64, u32, u64, usize, f64, String];
+
+impl<T: Schema> Schema for Option<T> {
+    const FINGERPRINT: u64 = fingerprint("Option", &[T::FINGERPRINT]);
+}
+
+impl<T: Schema> Schema for Vec<T> {
+    const FINGERPRINT: u64 = fingerprint("Vec", &[T::FINGERPRINT]);
+}
+
+/// Boxes are encoded as the value they hold.
+impl<T: Schema> Schema for Box<T> {
+    const FINGERPRINT: u64 = T::FINGERPRINT;
+}
+
+impl<K: Schema, V: Schema> Schema for BTreeMap<K, V> {
+    const FINGERPRINT: u64 = fingerprint("Map", &[K::FINGERPRINT, V::FINGERPRINT]);
+}
+
+impl<A: Schema, B: Schema> Schema for (A, B) {
+    const FINGERPRINT: u64 = fingerprint("Tuple", &[A::FINGERPRINT, B::FINGERPRINT]);
+}
+
+/// State tables are encoded as a list of the keys with values, paired with their values.
+impl<K, V> Schema for StateTable<K, V>
+where
+    K: Schema + Eq + std::hash::Hash + crate::state::KeyIter<Item = K>,
+    V: Schema,
+{
+    const FINGERPRINT: u64 = <Vec<(K, V)> as Schema>::FINGERPRINT;
+}
+
+/// Declares the schema of each type, written like its definition without attributes, visibility
+/// or generics.
+macro_rules! schema {
+    (@declared [$($declared:tt)*]) => {
+        /// Names of the declared types, along with the names of their variants or fields.
+        #[cfg(test)]
+        fn declared() -> Vec<(&'static str, Vec<&'static str>, Vec<&'static str>)> {
+            vec![$(tests::declaration::<$declared>()),*]
+        }
+    };
+    (@declared [$($declared:tt)*]
+        enum $name:ident {
+            $($variant:ident $(($($tuple:ty),* $(,)?))? $({$($field:ident: $field_type:ty),* $(,)?})?),*
+            $(,)?
+        }
+        $($rest:tt)*
+    ) => {
+        impl Schema for $name {
+            const FINGERPRINT: u64 = fingerprint(
+                stringify!(enum $name {
+                    $($variant $(($($tuple),*))? $({$($field: $field_type),*})?),*
+                }),
+                &[$($($(<$tuple as Schema>::FINGERPRINT,)*)? $($(<$field_type as Schema>::FINGERPRINT,)*)?)*],
+            );
+        }
+
+        #[cfg(test)]
+        impl tests::Declared for $name {
+            const NAME: &'static str = stringify!($name);
+            const MEMBERS: &'static [&'static str] = &[$(stringify!($variant)),*];
+        }
+
+        schema! { @declared [$($declared)* $name] $($rest)* }
+    };
+    (@declared [$($declared:tt)*]
+        struct $name:ident { $($field:ident: $field_type:ty),* $(,)? }
+        $($rest:tt)*
+    ) => {
+        impl Schema for $name {
+            const FINGERPRINT: u64 = fingerprint(
+                stringify!(struct $name { $($field: $field_type),* }),
+                &[$(<$field_type as Schema>::FINGERPRINT),*],
+            );
+        }
+
+        #[cfg(test)]
+        impl tests::Declared for $name {
+            const NAME: &'static str = stringify!($name);
+            const MEMBERS: &'static [&'static str] = &[$(stringify!($field)),*];
+        }
+
+        schema! { @declared [$($declared)* $name] $($rest)* }
+    };
+    (@declared [$($declared:tt)*]
+        struct $name:ident($($field_type:ty),* $(,)?);
+        $($rest:tt)*
+    ) => {
+        impl Schema for $name {
+            const FINGERPRINT: u64 = fingerprint(
+                stringify!(struct $name($($field_type),*)),
+                &[$(<$field_type as Schema>::FINGERPRINT),*],
+            );
+        }
+
+        #[cfg(test)]
+        impl tests::Declared for $name {
+            const NAME: &'static str = stringify!($name);
+            const MEMBERS: &'static [&'static str] = &[];
+        }
+
+        schema! { @declared [$($declared)* $name] $($rest)* }
+    };
+}
+
+schema! {
+    @declared []
+
+    enum Intent {
+        Handshake(ProtocolInfo),
+        Load(SavePayload),
+        Save,
+        Export,
+        Import(String),
+        ExportReplay,
+        ExportDiagnostics,
+        ListSlots,
+        CreateSlot(String),
+        OpenSlot(SlotId),
+        RenameSlot(SlotId, String),
+        DuplicateSlot(SlotId, String),
+        DeleteSlot(SlotId),
+        TimeControl(TimeControl),
+        Rewind { days: u32 },
+        QueueWorkOrder(WorkOrderKind),
+        PageHidden(bool),
+        Snapshot,
+        Spectate,
+    }
+
+    enum TimeControl {
+        Start,
+        Pause,
+        SetSpeed(f64),
+        Step(u32),
+    }
+
+    enum WorkOrderKind {
+        Craft(CraftingRecipeKind),
+        Construct(BuildingKind),
+    }
+
+    enum EngineMessage {
+        Handshake(HandshakeReply),
+        Loaded,
+        Saved(SavePayload),
+        Exported(String),
+        Replay(Replay),
+        Diagnostics(String),
+        LoadFailed(LoadError),
+        Slots(Vec<SlotMetadata>),
+        SlotOpened(SlotMetadata),
+        OfflineProgress(OfflineSummary),
+        Updated(Vec<EngineUpdate>),
+        Snapshot(Box<Snapshot>),
+    }
+
+    struct ProtocolInfo {
+        version: u32,
+        schema_hash: u64,
+    }
+
+    struct HandshakeReply {
+        engine: ProtocolInfo,
+        compatible: bool,
+    }
+
+    struct OfflineSummary {
+        elapsed_seconds: f64,
+        resources: BTreeMap<ResourceKind, f64>,
+        days: u64,
+        seasons: u64,
+        years: u64,
+    }
+
+    struct Snapshot {
+        buildings: BuildingTransport,
+        calendar: CalendarTransport,
+        fulfillments: FulfillmentTransport,
+        pacing: PacingTransport,
+        resources: ResourceTransport,
+        time: TimeTransport,
+        visibility: VisibilityTransport,
+    }
+
+    enum EngineUpdate {
+        CalendarChanged(CalendarTransport),
+        BuildingsChanged(BuildingTransport),
+        FulfillmentsChanged(FulfillmentTransport),
+        PacingChanged(PacingTransport),
+        ResourcesChanged(ResourceTransport),
+        TimeChanged(TimeTransport),
+        VisibilityChanged(VisibilityTransport),
+    }
+
+    struct BuildingTransport {
+        levels: StateTable<BuildingKind, u32>,
+    }
+
+    struct FulfillmentTransport {
+        fulfillments: StateTable<RecipeKind, FulfillmentState>,
+        required_amounts: StateTable<(RecipeKind, ResourceKind), f64>,
+    }
+
+    struct CalendarTransport {
+        day: Option<i16>,
+        season: Option<SeasonKind>,
+        year: Option<usize>,
+    }
+
+    struct ResourceTransport {
+        amounts: StateTable<ResourceKind, f64>,
+        deltas: StateTable<ResourceKind, f64>,
+        capacities: StateTable<ResourceKind, Option<f64>>,
+    }
+
+    struct PacingTransport {
+        catching_up: Option<bool>,
+        behind_seconds: Option<u64>,
+    }
+
+    struct TimeTransport {
+        running_state: Option<RunningState>,
+        speed: Option<f64>,
+    }
+
+    struct VisibilityTransport {
+        nodes: StateTable<NodeId, bool>,
+    }
+
+    struct SavePayload(String);
+
+    struct Replay {
+        initial: Option<SavePayload>,
+        running_state: RunningState,
+        speed: f64,
+        entries: Vec<ReplayEntry>,
+        ticks: u64,
+        outcome: SavePayload,
+    }
+
+    struct ReplayEntry {
+        tick: u64,
+        input: ReplayInput,
+    }
+
+    enum ReplayInput {
+        Intent(Intent),
+        TimeAway { gap_millis: i64 },
+    }
+
+    struct SlotId(u32);
+
+    struct SlotMetadata {
+        id: SlotId,
+        name: String,
+        last_played: i64,
+        year: usize,
+        play_time_seconds: f64,
+    }
+
+    enum LoadError {
+        Malformed(String),
+        Corrupted(String),
+        Storage(String),
+        NewerVersion { version: u32, supported: u32 },
+        Migration { from: u32, reason: String },
+    }
+
+    enum RunningState {
+        Running,
+        Paused,
+    }
+
+    enum SeasonKind {
+        Spring,
+        Summer,
+        Autumn,
+        Winter,
+    }
+
+    enum RecipeKind {
+        Crafting(CraftingRecipeKind),
+        Building(BuildingKind),
+    }
+
+    enum FulfillmentState {
+        Unfulfilled,
+        Fulfilled,
+        Capped,
+    }
+
+    enum NodeId {
+        Navigation(NavigationNodeId),
+        Resources(ResourceNodeId),
+        Bonfire(BonfireNodeId),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde::{
+        de::{value::Error, Error as _, Visitor},
+        forward_to_deserialize_any, Deserialize, Deserializer,
+    };
+
+    use super::*;
+
+    /// A type whose schema is declared, which the declaration can be checked against.
+    pub(super) trait Declared {
+        const NAME: &'static str;
+        /// Names of the variants or fields, in the order they were declared.
+        const MEMBERS: &'static [&'static str];
+    }
+
+    /// Reads the name of the type and of its variants or fields from its `Deserialize`
+    /// implementation, giving up before reading anything else.
+    struct Members<'a> {
+        name: &'a mut &'static str,
+        members: &'a mut &'static [&'static str],
+    }
+
+    impl<'de> Deserializer<'de> for Members<'_> {
+        type Error = Error;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Error> {
+            Err(Error::custom("not a declared type"))
+        }
+
+        fn deserialize_enum<V: Visitor<'de>>(
+            self,
+            name: &'static str,
+            variants: &'static [&'static str],
+            _: V,
+        ) -> Result<V::Value, Error> {
+            *self.name = name;
+            *self.members = variants;
+            Err(Error::custom("read the variants"))
+        }
+
+        fn deserialize_struct<V: Visitor<'de>>(
+            self,
+            name: &'static str,
+            fields: &'static [&'static str],
+            _: V,
+        ) -> Result<V::Value, Error> {
+            *self.name = name;
+            *self.members = fields;
+            Err(Error::custom("read the fields"))
+        }
+
+        fn deserialize_newtype_struct<V: Visitor<'de>>(
+            self,
+            name: &'static str,
+            _: V,
+        ) -> Result<V::Value, Error> {
+            *self.name = name;
+            Err(Error::custom("read the name"))
+        }
+
+        forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string bytes byte_buf
+            option unit unit_struct seq tuple tuple_struct map identifier ignored_any
+        }
+    }
+
+    /// The name and members of `T` as declared, and as they are read by serde.
+    pub(super) fn declaration<T>() -> (&'static str, Vec<&'static str>, Vec<&'static str>)
+    where
+        T: Declared + for<'de> Deserialize<'de>,
+    {
+        let mut name = "";
+        let mut members: &[&str] = &[];
+        let _ = T::deserialize(Members {
+            name: &mut name,
+            members: &mut members,
+        });
+        assert_eq!(name, T::NAME, "{} is declared under another name", T::NAME);
+        (T::NAME, T::MEMBERS.to_vec(), members.to_vec())
+    }
+
+    #[test]
+    fn declares_the_members_of_every_type_in_order() {
+        for (name, declared, read) in declared() {
+            assert_eq!(
+                declared, read,
+                "the schema of {name} differs from its definition"
+            );
+        }
+    }
+
+    #[test]
+    fn fingerprints_tell_apart_what_the_types_hold() {
+        assert_ne!(
+            <Vec<u32> as Schema>::FINGERPRINT,
+            <Vec<u64> as Schema>::FINGERPRINT
+        );
+        assert_ne!(
+            <Option<u32> as Schema>::FINGERPRINT,
+            <Vec<u32> as Schema>::FINGERPRINT
+        );
+        assert_eq!(
+            <Box<Snapshot> as Schema>::FINGERPRINT,
+            <Snapshot as Schema>::FINGERPRINT
+        );
+        assert_ne!(fingerprint("a", &[1]), fingerprint("a", &[2]));
+    }
+}
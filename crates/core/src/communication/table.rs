@@ -2,11 +2,15 @@ use std::collections::{hash_map::Iter, HashMap};
 use std::hash::Hash;
 
 use ahash::RandomState;
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
 use crate::state::KeyIter;
 
-#[derive(Serialize, Deserialize, Debug)]
+/// Values for every key of `K`, with `None` for the ones that are not part of the update.
+///
+/// Only the keys with values are serialized, as a list of pairs so that keys do not have to be
+/// strings in formats like JSON.
+#[derive(Debug, Clone)]
 pub struct StateTable<K, V>(HashMap<K, Option<V>, RandomState>)
 where
     K: Eq + Hash + KeyIter<Item = K>;
@@ -27,7 +31,7 @@ where
         self.0.get_mut(key).unwrap()
     }
 
-    pub fn iter(&self) -> StateTableIter<K, V> {
+    pub fn iter(&self) -> StateTableIter<'_, K, V> {
         StateTableIter {
             inner: self.0.iter(),
         }
@@ -60,3 +64,31 @@ where
         Self::new()
     }
 }
+
+impl<K, V> Serialize for StateTable<K, V>
+where
+    K: Eq + Hash + KeyIter<Item = K> + Serialize,
+    V: Serialize,
+{
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            self.0
+                .iter()
+                .filter_map(|(key, value)| value.as_ref().map(|value| (key, value))),
+        )
+    }
+}
+
+impl<'de, K, V> Deserialize<'de> for StateTable<K, V>
+where
+    K: Eq + Hash + KeyIter<Item = K> + Deserialize<'de>,
+    V: Deserialize<'de>,
+{
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut table = Self::new();
+        for (key, value) in Vec::<(K, V)>::deserialize(deserializer)? {
+            table.0.insert(key, Some(value));
+        }
+        Ok(table)
+    }
+}
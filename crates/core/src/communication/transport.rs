@@ -11,37 +11,46 @@ use crate::state::{
 
 use super::StateTable;
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct BuildingTransport {
     pub levels: StateTable<BuildingKind, u32>,
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct FulfillmentTransport {
     pub fulfillments: StateTable<RecipeKind, FulfillmentState>,
     pub required_amounts: StateTable<(RecipeKind, ResourceKind), f64>,
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct CalendarTransport {
     pub day: Option<i16>,
     pub season: Option<SeasonKind>,
     pub year: Option<usize>,
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct ResourceTransport {
     pub amounts: StateTable<ResourceKind, f64>,
     pub deltas: StateTable<ResourceKind, f64>,
     pub capacities: StateTable<ResourceKind, Option<f64>>,
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+/// How far the simulation lags behind the wall clock, after slow or throttled frames.
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
+pub struct PacingTransport {
+    pub catching_up: Option<bool>,
+    /// Wall-clock time still to be caught up on, in whole seconds.
+    pub behind_seconds: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct TimeTransport {
     pub running_state: Option<RunningState>,
+    pub speed: Option<f64>,
 }
 
-#[derive(Serialize, Deserialize, Default, Debug)]
+#[derive(Serialize, Deserialize, Default, Debug, Clone)]
 pub struct VisibilityTransport {
     pub nodes: StateTable<NodeId, bool>,
 }
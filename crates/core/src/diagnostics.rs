@@ -0,0 +1,49 @@
+//! Everything worth attaching to a bug report, gathered into a single file.
+
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    persistence::{ReplayEntry, SavePayload, SlotMetadata},
+    state::time::RunningState,
+};
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct DiagnosticBundle {
+    /// Version of the engine build that assembled the bundle.
+    pub version: String,
+    pub schema_version: u32,
+    /// Wall-clock time the bundle was assembled, in milliseconds since the Unix epoch.
+    pub created_at: i64,
+    pub settings: EngineSettings,
+    pub state: SavePayload,
+    /// The most recent intents since the last load, tagged with the tick they arrived on.
+    pub intents: Vec<ReplayEntry>,
+    /// The most recent messages sent to the client, as JSON.
+    pub messages: Vec<String>,
+    /// The most recent lines of engine log output.
+    pub log: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct EngineSettings {
+    pub seconds_per_tick: f64,
+    pub autosave_interval: u32,
+    pub running_state: RunningState,
+    pub speed: f64,
+    pub active_slot: Option<SlotMetadata>,
+    /// How the last frame was paced, when frames follow the wall clock.
+    #[serde(default)]
+    pub frame: Option<FrameTiming>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct FrameTiming {
+    /// Wall-clock time between the starts of the last two frames.
+    pub gap_millis: f64,
+    /// Wall-clock time fed into the fixed timestep.
+    pub fed_millis: f64,
+    /// How long the frame took to run.
+    pub busy_millis: f64,
+    /// Wall-clock time still to be caught up on.
+    pub owed_millis: f64,
+}
@@ -1,3 +1,5 @@
 pub mod communication;
+pub mod diagnostics;
+pub mod persistence;
 pub mod state;
 pub mod utils;
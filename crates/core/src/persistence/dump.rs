@@ -0,0 +1,230 @@
+//! State dumps, the save format meant for people to read and edit.
+//!
+//! A dump is a [`SavePayload`](super::SavePayload) written by
+//! [`encode_dump`](super::SavePayload::encode_dump) with indentation and the state's keys in order, so it is
+//! loaded like any other save and older dumps are upgraded through the same migrations:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "state": {
+//!     "buildings": { "CatnipField": { "level": 1 } },
+//!     "calendar": { "day": 3, "season": "Spring", "year": 0 },
+//!     "play_time_seconds": 12.5,
+//!     "resources": { "Catnip": { "amount": 10.0, "unlocked": true } },
+//!     "saved_at": null,
+//!     "unlocked_recipes": [ { "Building": "CatnipField" } ],
+//!     "visible_nodes": [ { "Navigation": "Bonfire" } ]
+//!   }
+//! }
+//! ```
+//!
+//! Tools address single values of the state by field paths, with names in snake case:
+//!
+//! | Path                           | Value                                        |
+//! |--------------------------------|----------------------------------------------|
+//! | `resource.<resource>`          | amount, at least 0                           |
+//! | `resource.<resource>.unlocked` | `true` or `false`                            |
+//! | `building.<building>`          | level                                        |
+//! | `calendar.day`                 | day of the season, from 0 to 99              |
+//! | `calendar.season`              | `spring`, `summer`, `autumn` or `winter`     |
+//! | `calendar.year`                | year                                         |
+//! | `recipe.<kind>.<recipe>`       | whether the recipe is unlocked               |
+//! | `visible.<area>.<node>`        | whether the UI node is visible               |
+//! | `play_time`                    | seconds spent playing, at least 0            |
+//! | `saved_at`                     | milliseconds since the Unix epoch, or `none` |
+//!
+//! For example `resource.wood`, `building.catnip_field` or `recipe.crafting.refine_catnip`.
+
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fmt::Debug,
+    str::FromStr,
+};
+
+use crate::state::{
+    buildings::BuildingKind,
+    calendar::{SeasonKind, SEASON_DAYS},
+    recipes::RecipeKind,
+    resources::ResourceKind,
+    ui::NodeId,
+    KeyIter,
+};
+
+use super::SaveState;
+
+const SEASONS: [SeasonKind; 4] = [
+    SeasonKind::Spring,
+    SeasonKind::Summer,
+    SeasonKind::Autumn,
+    SeasonKind::Winter,
+];
+
+/// Every field of the state by its path, including the ones left at their defaults.
+pub fn fields(state: &SaveState) -> BTreeMap<String, String> {
+    let mut fields = BTreeMap::new();
+    let mut add = |path: String, value: String| {
+        fields.insert(path, value);
+    };
+
+    for kind in ResourceKind::key_iter() {
+        let resource = state.resources.get(&kind).copied().unwrap_or_default();
+        add(
+            format!("resource.{}", key_name(kind)),
+            resource.amount.to_string(),
+        );
+        add(
+            format!("resource.{}.unlocked", key_name(kind)),
+            resource.unlocked.to_string(),
+        );
+    }
+    for kind in BuildingKind::key_iter() {
+        let building = state.buildings.get(&kind).copied().unwrap_or_default();
+        add(
+            format!("building.{}", key_name(kind)),
+            building.level.to_string(),
+        );
+    }
+    add("calendar.day".to_string(), state.calendar.day.to_string());
+    add(
+        "calendar.season".to_string(),
+        key_name(state.calendar.season),
+    );
+    add("calendar.year".to_string(), state.calendar.year.to_string());
+    for kind in RecipeKind::key_iter() {
+        let unlocked = state.unlocked_recipes.contains(&kind);
+        add(format!("recipe.{}", key_name(kind)), unlocked.to_string());
+    }
+    for node in NodeId::key_iter() {
+        let visible = state.visible_nodes.contains(&node);
+        add(format!("visible.{}", key_name(node)), visible.to_string());
+    }
+    add("play_time".to_string(), state.play_time_seconds.to_string());
+    add(
+        "saved_at".to_string(),
+        state
+            .saved_at
+            .map_or_else(|| "none".to_string(), |millis| millis.to_string()),
+    );
+
+    fields
+}
+
+/// Sets the field at `path` to `value`, both written as listed in the [module docs](self).
+pub fn set(state: &mut SaveState, path: &str, value: &str) -> Result<(), String> {
+    let unknown = || format!("unknown field {path}");
+    let (head, rest) = path.split_once('.').unwrap_or((path, ""));
+
+    match head {
+        "resource" => {
+            let (name, field) = match rest.strip_suffix(".unlocked") {
+                Some(name) => (name, "unlocked"),
+                None => (rest, "amount"),
+            };
+            let kind = find_key::<ResourceKind>(name).ok_or_else(unknown)?;
+            // Values are parsed first, so that a failed edit leaves the state as it was.
+            match field {
+                "unlocked" => {
+                    let unlocked = parse(path, value)?;
+                    state.resources.entry(kind).or_default().unlocked = unlocked;
+                }
+                _ => {
+                    let amount = parse_quantity(path, value)?;
+                    state.resources.entry(kind).or_default().amount = amount;
+                }
+            }
+        }
+        "building" => {
+            let kind = find_key::<BuildingKind>(rest).ok_or_else(unknown)?;
+            let level = parse(path, value)?;
+            state.buildings.entry(kind).or_default().level = level;
+        }
+        "calendar" => match rest {
+            "day" => {
+                let day = parse(path, value)?;
+                if !SEASON_DAYS.contains(&day) {
+                    return Err(format!(
+                        "{path} needs a day from {SEASON_DAYS:?}, got {value}"
+                    ));
+                }
+                state.calendar.day = day;
+            }
+            "year" => state.calendar.year = parse(path, value)?,
+            "season" => {
+                state.calendar.season = SEASONS
+                    .into_iter()
+                    .find(|season| key_name(*season) == value)
+                    .ok_or_else(|| format!("{path} needs a season, got {value}"))?;
+            }
+            _ => return Err(unknown()),
+        },
+        "recipe" => {
+            let kind = find_key::<RecipeKind>(rest).ok_or_else(unknown)?;
+            toggle(&mut state.unlocked_recipes, kind, parse(path, value)?);
+        }
+        "visible" => {
+            let node = find_key::<NodeId>(rest).ok_or_else(unknown)?;
+            toggle(&mut state.visible_nodes, node, parse(path, value)?);
+        }
+        "play_time" if rest.is_empty() => state.play_time_seconds = parse_quantity(path, value)?,
+        "saved_at" if rest.is_empty() => {
+            state.saved_at = match value {
+                "none" => None,
+                millis => Some(parse(path, millis)?),
+            }
+        }
+        _ => return Err(unknown()),
+    }
+    Ok(())
+}
+
+/// Name of a key in field paths, such as `catnip_field` or `building.catnip_field`.
+fn key_name(key: impl Debug) -> String {
+    // Debug output mirrors the enum variants, e.g. `Building(CatnipField)`.
+    let debug = format!("{key:?}");
+    let mut name = String::with_capacity(debug.len() + 4);
+    for c in debug.chars() {
+        match c {
+            '(' => name.push('.'),
+            ')' => {}
+            c if c.is_uppercase() => {
+                if !name.is_empty() && !name.ends_with('.') {
+                    name.push('_');
+                }
+                name.extend(c.to_lowercase());
+            }
+            c => name.push(c),
+        }
+    }
+    name
+}
+
+fn find_key<K: KeyIter<Item = K> + Debug>(name: &str) -> Option<K> {
+    K::key_iter().find(|key| key_name(key) == name)
+}
+
+fn parse<T: FromStr>(path: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{path} cannot be set to {value}"))
+}
+
+/// Parses an amount of something, which cannot be negative or not a number.
+fn parse_quantity(path: &str, value: &str) -> Result<f64, String> {
+    let quantity: f64 = parse(path, value)?;
+    if quantity.is_finite() && quantity >= 0.0 {
+        Ok(quantity)
+    } else {
+        Err(format!(
+            "{path} needs a finite amount of at least 0, got {value}"
+        ))
+    }
+}
+
+fn toggle<T: Ord>(set: &mut BTreeSet<T>, item: T, present: bool) {
+    if present {
+        set.insert(item);
+    } else {
+        set.remove(&item);
+    }
+}
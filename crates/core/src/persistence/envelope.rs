@@ -0,0 +1,97 @@
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::{migrations, SaveState};
+
+/// Version of the [`SaveState`] schema written by this build.
+///
+/// Bump this whenever a change to [`SaveState`] or to the `state_key!` enumerations it contains
+/// would make existing saves deserialize differently, and add the matching step to the migration
+/// chain in [`migrations`].
+pub const SCHEMA_VERSION: u32 = 1;
+
+/// A persisted save, carrying its schema version alongside the state.
+///
+/// The state is stored as JSON so that keys are written by name rather than by variant index,
+/// which keeps older saves readable when the enumerations change.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub struct SavePayload(pub String);
+
+#[derive(Serialize, Deserialize)]
+struct Envelope {
+    version: u32,
+    state: Value,
+}
+
+impl SavePayload {
+    pub fn encode(state: &SaveState) -> Self {
+        let envelope = Envelope {
+            version: SCHEMA_VERSION,
+            state: serde_json::to_value(state).expect("save state should always serialize"),
+        };
+        Self(serde_json::to_string(&envelope).expect("save envelope should always serialize"))
+    }
+
+    /// Encodes the state as a [state dump](super::dump), indented for people to read and edit.
+    pub fn encode_dump(state: &SaveState) -> Self {
+        let envelope = Envelope {
+            version: SCHEMA_VERSION,
+            state: serde_json::to_value(state).expect("save state should always serialize"),
+        };
+        Self(
+            serde_json::to_string_pretty(&envelope).expect("save envelope should always serialize"),
+        )
+    }
+
+    /// Decodes the payload, upgrading it to the current schema version when necessary.
+    pub fn decode(&self) -> Result<SaveState, LoadError> {
+        let Envelope { version, mut state } =
+            serde_json::from_str(&self.0).map_err(|e| LoadError::Malformed(e.to_string()))?;
+
+        if version > SCHEMA_VERSION {
+            return Err(LoadError::NewerVersion {
+                version,
+                supported: SCHEMA_VERSION,
+            });
+        }
+
+        migrations::migrate(version, &mut state)?;
+
+        serde_json::from_value(state).map_err(|e| LoadError::Malformed(e.to_string()))
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum LoadError {
+    /// The payload is not a save, or is damaged.
+    Malformed(String),
+    /// The exported text could not be decoded into a payload, or failed its checksum.
+    Corrupted(String),
+    /// The save could not be read from storage.
+    Storage(String),
+    /// The payload was written by a newer build than this one.
+    NewerVersion { version: u32, supported: u32 },
+    /// The payload is from an older build, but could not be upgraded.
+    Migration { from: u32, reason: String },
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LoadError::Malformed(reason) => write!(f, "malformed save: {reason}"),
+            LoadError::Corrupted(reason) => write!(f, "corrupted save text: {reason}"),
+            LoadError::Storage(reason) => write!(f, "could not read save from storage: {reason}"),
+            LoadError::NewerVersion { version, supported } => write!(
+                f,
+                "save has schema version {version}, but this build only supports up to {supported}"
+            ),
+            LoadError::Migration { from, reason } => {
+                write!(f, "could not migrate save from version {from}: {reason}")
+            }
+        }
+    }
+}
+
+impl std::error::Error for LoadError {}
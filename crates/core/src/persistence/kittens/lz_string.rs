@@ -0,0 +1,121 @@
+//! Decoder for the `compressToBase64` format of the JavaScript lz-string library.
+
+const BASE64_ALPHABET: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=";
+
+/// Decompresses text produced by `LZString.compressToBase64`.
+pub fn decompress_from_base64(input: &str) -> Result<String, String> {
+    let values = input
+        .bytes()
+        .filter(|b| !b.is_ascii_whitespace())
+        .map(|b| {
+            BASE64_ALPHABET
+                .iter()
+                .position(|c| *c == b)
+                .map(|p| p as u32)
+                .ok_or_else(|| format!("unexpected character {:?}", b as char))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if values.is_empty() {
+        return Err("input is empty".to_string());
+    }
+
+    let units = decompress(&values, 32)?;
+    String::from_utf16(&units).map_err(|e| e.to_string())
+}
+
+struct BitReader<'a> {
+    values: &'a [u32],
+    reset_value: u32,
+    value: u32,
+    position: u32,
+    index: usize,
+}
+
+impl BitReader<'_> {
+    fn read(&mut self, bit_count: u32) -> u32 {
+        let mut bits = 0;
+        for power in 0..bit_count {
+            let bit = self.value & self.position;
+            self.position >>= 1;
+            if self.position == 0 {
+                self.position = self.reset_value;
+                self.value = self.values.get(self.index).copied().unwrap_or(0);
+                self.index += 1;
+            }
+            if bit > 0 {
+                bits |= 1 << power;
+            }
+        }
+        bits
+    }
+}
+
+fn decompress(values: &[u32], reset_value: u32) -> Result<Vec<u16>, String> {
+    let mut reader = BitReader {
+        values,
+        reset_value,
+        value: values[0],
+        position: reset_value,
+        index: 1,
+    };
+
+    // The first three entries are placeholders for the control codes.
+    let mut dictionary: Vec<Vec<u16>> = vec![Vec::new(); 3];
+    let mut enlarge_in: u32 = 4;
+    let mut bit_count: u32 = 3;
+
+    let first = match reader.read(2) {
+        0 => reader.read(8) as u16,
+        1 => reader.read(16) as u16,
+        2 => return Ok(Vec::new()),
+        code => return Err(format!("unexpected control code {code}")),
+    };
+    dictionary.push(vec![first]);
+    let mut previous = vec![first];
+    let mut result = previous.clone();
+
+    loop {
+        if reader.index > values.len() {
+            return Err("input ended unexpectedly".to_string());
+        }
+
+        let mut code = reader.read(bit_count) as usize;
+        match code {
+            0 | 1 => {
+                let unit = reader.read(if code == 0 { 8 } else { 16 }) as u16;
+                dictionary.push(vec![unit]);
+                code = dictionary.len() - 1;
+                enlarge_in -= 1;
+            }
+            2 => return Ok(result),
+            _ => {}
+        }
+
+        if enlarge_in == 0 {
+            enlarge_in = 1 << bit_count;
+            bit_count += 1;
+        }
+
+        let entry = if code < dictionary.len() {
+            dictionary[code].clone()
+        } else if code == dictionary.len() {
+            let mut entry = previous.clone();
+            entry.push(previous[0]);
+            entry
+        } else {
+            return Err(format!("unexpected dictionary reference {code}"));
+        };
+        result.extend_from_slice(&entry);
+
+        let mut added = previous;
+        added.push(entry[0]);
+        dictionary.push(added);
+        enlarge_in -= 1;
+        previous = entry;
+
+        if enlarge_in == 0 {
+            enlarge_in = 1 << bit_count;
+            bit_count += 1;
+        }
+    }
+}
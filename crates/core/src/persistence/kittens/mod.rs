@@ -0,0 +1,159 @@
+//! Import of Kittens Game exports into a new [`SaveState`].
+//!
+//! Kittens Game exports its save as lz-string compressed JSON. Only the parts of the save that
+//! have an equivalent here are carried over; everything else is reported back in
+//! [`KittensImport::unmapped`] so players know what was left behind.
+
+mod lz_string;
+
+use serde::Deserialize;
+
+use crate::state::{
+    buildings::BuildingKind,
+    calendar::{SeasonKind, SEASON_DAYS},
+    recipes::RecipeKind,
+    resources::ResourceKind,
+    ui::NodeId,
+};
+
+use super::{BuildingState, CalendarState, LoadError, ResourceState, SaveState};
+
+/// Result of importing a Kittens Game export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct KittensImport {
+    pub state: SaveState,
+    pub unmapped: Unmapped,
+}
+
+/// Names of Kittens Game entries that were in use but have no equivalent here.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Unmapped {
+    pub resources: Vec<String>,
+    pub buildings: Vec<String>,
+}
+
+impl Unmapped {
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty() && self.buildings.is_empty()
+    }
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct KittensSave {
+    resources: Vec<KittensResource>,
+    buildings: Vec<KittensBuilding>,
+    calendar: Option<KittensCalendar>,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct KittensResource {
+    name: String,
+    value: f64,
+    unlocked: bool,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct KittensBuilding {
+    name: String,
+    val: u32,
+    unlocked: bool,
+}
+
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+struct KittensCalendar {
+    day: f64,
+    season: u8,
+    year: usize,
+}
+
+fn resource_kind(name: &str) -> Option<ResourceKind> {
+    match name {
+        "catnip" => Some(ResourceKind::Catnip),
+        "wood" => Some(ResourceKind::Wood),
+        _ => None,
+    }
+}
+
+fn building_kind(name: &str) -> Option<BuildingKind> {
+    match name {
+        "field" => Some(BuildingKind::CatnipField),
+        _ => None,
+    }
+}
+
+fn season_kind(season: u8) -> SeasonKind {
+    match season {
+        1 => SeasonKind::Summer,
+        2 => SeasonKind::Autumn,
+        3 => SeasonKind::Winter,
+        _ => SeasonKind::Spring,
+    }
+}
+
+/// Decodes a Kittens Game export string and maps it onto a new game.
+pub fn import(text: &str) -> Result<KittensImport, LoadError> {
+    let json = lz_string::decompress_from_base64(text).map_err(LoadError::Corrupted)?;
+    let save: KittensSave =
+        serde_json::from_str(&json).map_err(|e| LoadError::Malformed(e.to_string()))?;
+
+    let mut state = SaveState::default();
+    let mut unmapped = Unmapped::default();
+
+    for resource in save.resources {
+        let in_use = resource.unlocked || resource.value > 0.0;
+        match resource_kind(&resource.name) {
+            Some(kind) => {
+                state.resources.insert(
+                    kind,
+                    ResourceState {
+                        amount: resource.value.max(0.0),
+                        unlocked: in_use,
+                    },
+                );
+                if in_use {
+                    state.visible_nodes.insert(NodeId::from(kind));
+                }
+            }
+            None if in_use => unmapped.resources.push(resource.name),
+            None => {}
+        }
+    }
+
+    for building in save.buildings {
+        let in_use = building.unlocked || building.val > 0;
+        match building_kind(&building.name) {
+            Some(kind) => {
+                state.buildings.insert(
+                    kind,
+                    BuildingState {
+                        level: building.val,
+                    },
+                );
+                if in_use {
+                    let recipe = RecipeKind::Building(kind);
+                    state.unlocked_recipes.insert(recipe);
+                    state.visible_nodes.insert(NodeId::from(recipe));
+                }
+            }
+            None if in_use => unmapped.buildings.push(building.name),
+            None => {}
+        }
+    }
+
+    if let Some(calendar) = save.calendar {
+        state.calendar = CalendarState {
+            day: calendar.day.clamp(
+                f64::from(*SEASON_DAYS.start()),
+                f64::from(*SEASON_DAYS.end()),
+            ) as i16,
+            season: season_kind(calendar.season),
+            year: calendar.year,
+        };
+    }
+
+    Ok(KittensImport { state, unmapped })
+}
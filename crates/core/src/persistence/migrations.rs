@@ -0,0 +1,274 @@
+//! Upgrades of persisted [`SaveState`](super::SaveState) JSON from older schema versions.
+//!
+//! Each [`Migration`] upgrades a payload by exactly one version, and they are applied in order
+//! until the payload reaches [`SCHEMA_VERSION`]. Migrations work on the untyped JSON because the
+//! older schema may contain keys that no longer exist in the current enumerations.
+//!
+//! Resources, buildings and sections that are new in the current schema do not need a migration,
+//! because missing keys fall back to their new game values when the state is deserialized.
+
+use serde_json::{Map, Value};
+
+use super::{LoadError, SCHEMA_VERSION};
+
+pub struct Migration {
+    /// Version this migration upgrades from, producing `from + 1`.
+    pub from: u32,
+    pub migrate: fn(&mut Value) -> Result<(), String>,
+}
+
+static MIGRATIONS: &[Migration] = &[];
+
+pub(super) fn migrate(version: u32, state: &mut Value) -> Result<(), LoadError> {
+    apply(MIGRATIONS, version, SCHEMA_VERSION, state)
+}
+
+fn apply(
+    migrations: &[Migration],
+    version: u32,
+    target: u32,
+    state: &mut Value,
+) -> Result<(), LoadError> {
+    for from in version..target {
+        let migration =
+            migrations
+                .iter()
+                .find(|m| m.from == from)
+                .ok_or_else(|| LoadError::Migration {
+                    from,
+                    reason: "no migration registered".to_string(),
+                })?;
+        (migration.migrate)(state).map_err(|reason| LoadError::Migration { from, reason })?;
+    }
+    Ok(())
+}
+
+/// Renames an enumeration variant everywhere it appears in the state, both as a map key and as a
+/// value, e.g. `"Catnip"` in `resources` and in `{"Resources": "Catnip"}`.
+pub fn rename_variant(state: &mut Value, from: &str, to: &str) {
+    match state {
+        Value::String(s) if s == from => *s = to.to_string(),
+        Value::Array(items) => {
+            for item in items {
+                rename_variant(item, from, to);
+            }
+        }
+        Value::Object(map) => {
+            let renamed = std::mem::take(map)
+                .into_iter()
+                .map(|(key, mut value)| {
+                    rename_variant(&mut value, from, to);
+                    if key == from {
+                        (to.to_string(), value)
+                    } else {
+                        (key, value)
+                    }
+                })
+                .collect::<Map<_, _>>();
+            *map = renamed;
+        }
+        _ => {}
+    }
+}
+
+/// Removes every occurrence of an enumeration variant from the state, dropping map entries keyed
+/// by it and array items that are it.
+///
+/// Items are only dropped when they are the variant itself, either as `"Hut"` or wrapped in the
+/// variants of enclosing enumerations, as in `{"Building": "Hut"}`. Items that merely mention it
+/// somewhere, such as a struct with a field holding it, are kept.
+pub fn remove_variant(state: &mut Value, variant: &str) {
+    fn is_variant(value: &Value, variant: &str) -> bool {
+        match value {
+            Value::String(s) => s == variant,
+            Value::Object(map) if map.len() == 1 => map
+                .iter()
+                .all(|(k, v)| k == variant || is_variant(v, variant)),
+            _ => false,
+        }
+    }
+
+    match state {
+        Value::Array(items) => {
+            items.retain(|item| !is_variant(item, variant));
+            for item in items {
+                remove_variant(item, variant);
+            }
+        }
+        Value::Object(map) => {
+            map.remove(variant);
+            for value in map.values_mut() {
+                remove_variant(value, variant);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// A resource cost of a building, as it was priced in the schema version being migrated from.
+pub struct Refund<'a> {
+    pub resource: &'a str,
+    pub base_amount: f64,
+    pub price_ratio: f64,
+}
+
+/// Removes a building from the state and credits the resources that were spent on all of its
+/// levels, so that players do not lose progress when a building is taken out of the game.
+pub fn refund_building(state: &mut Value, building: &str, refunds: &[Refund<'_>]) {
+    let level = state
+        .pointer(&format!("/buildings/{building}/level"))
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+
+    for refund in refunds {
+        let spent: f64 = (0..level)
+            .map(|l| refund.base_amount * refund.price_ratio.powi(l as i32))
+            .sum();
+        if spent <= 0.0 {
+            continue;
+        }
+
+        let Some(resources) = state.get_mut("resources").and_then(Value::as_object_mut) else {
+            continue;
+        };
+        let resource = resources
+            .entry(refund.resource)
+            .or_insert_with(|| serde_json::json!({ "amount": 0.0, "unlocked": false }));
+        if let Some(amount) = resource.get_mut("amount") {
+            *amount = Value::from(amount.as_f64().unwrap_or(0.0) + spent);
+        }
+    }
+
+    remove_variant(state, building);
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn bump(state: &mut Value) -> Result<(), String> {
+        let steps = state["steps"].as_u64().unwrap_or(0);
+        state["steps"] = Value::from(steps + 1);
+        Ok(())
+    }
+
+    #[test]
+    fn applies_the_chain_in_order() {
+        let migrations = [
+            Migration {
+                from: 2,
+                migrate: |state| {
+                    assert_eq!(state["steps"], 1, "migration from 1 should run first");
+                    bump(state)
+                },
+            },
+            Migration {
+                from: 1,
+                migrate: bump,
+            },
+        ];
+
+        let mut state = json!({});
+        apply(&migrations, 1, 3, &mut state).unwrap();
+        assert_eq!(state["steps"], 2);
+
+        let mut state = json!({});
+        apply(&migrations, 3, 3, &mut state).unwrap();
+        assert_eq!(state, json!({}));
+    }
+
+    #[test]
+    fn fails_on_a_missing_or_failing_migration() {
+        let migrations = [Migration {
+            from: 1,
+            migrate: |_| Err("broken".to_string()),
+        }];
+
+        assert_eq!(
+            apply(&migrations, 0, 2, &mut json!({})),
+            Err(LoadError::Migration {
+                from: 0,
+                reason: "no migration registered".to_string()
+            })
+        );
+        assert_eq!(
+            apply(&migrations, 1, 2, &mut json!({})),
+            Err(LoadError::Migration {
+                from: 1,
+                reason: "broken".to_string()
+            })
+        );
+    }
+
+    #[test]
+    fn renames_keys_and_values() {
+        let mut state = json!({
+            "resources": { "Catnip": { "amount": 1.0 } },
+            "unlocked_recipes": [{ "Crafting": "Catnip" }, { "Building": "Hut" }],
+        });
+        rename_variant(&mut state, "Catnip", "Nip");
+
+        assert_eq!(
+            state,
+            json!({
+                "resources": { "Nip": { "amount": 1.0 } },
+                "unlocked_recipes": [{ "Crafting": "Nip" }, { "Building": "Hut" }],
+            })
+        );
+    }
+
+    #[test]
+    fn removes_only_the_exact_variant() {
+        let mut state = json!({
+            "buildings": { "Hut": { "level": 2 }, "Barn": { "level": 1 } },
+            "unlocked_recipes": [{ "Building": "Hut" }, { "Building": "Barn" }, "Hut"],
+            "notes": [{ "name": "Hut", "level": 2 }, ["Hut", "Barn"]],
+        });
+        remove_variant(&mut state, "Hut");
+
+        assert_eq!(
+            state,
+            json!({
+                "buildings": { "Barn": { "level": 1 } },
+                "unlocked_recipes": [{ "Building": "Barn" }],
+                "notes": [{ "level": 2, "name": "Hut" }, ["Barn"]],
+            })
+        );
+    }
+
+    #[test]
+    fn refunds_every_level_of_a_building() {
+        let mut state = json!({
+            "resources": { "Wood": { "amount": 1.0, "unlocked": true } },
+            "buildings": { "Hut": { "level": 3 } },
+            "unlocked_recipes": [{ "Building": "Hut" }],
+        });
+        let refunds = [
+            Refund {
+                resource: "Wood",
+                base_amount: 10.0,
+                price_ratio: 2.0,
+            },
+            Refund {
+                resource: "Minerals",
+                base_amount: 1.0,
+                price_ratio: 1.0,
+            },
+        ];
+        refund_building(&mut state, "Hut", &refunds);
+
+        assert_eq!(
+            state,
+            json!({
+                "resources": {
+                    "Wood": { "amount": 71.0, "unlocked": true },
+                    "Minerals": { "amount": 3.0, "unlocked": false },
+                },
+                "buildings": {},
+                "unlocked_recipes": [],
+            })
+        );
+    }
+}
@@ -0,0 +1,87 @@
+pub mod dump;
+mod envelope;
+pub mod kittens;
+pub mod migrations;
+mod replay;
+mod slots;
+
+pub use envelope::*;
+pub use replay::*;
+pub use slots::*;
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::state::{
+    buildings::BuildingKind,
+    calendar::SeasonKind,
+    recipes::RecipeKind,
+    resources::ResourceKind,
+    ui::{NodeId, NODE_VISIBILITY},
+};
+
+/// Full simulation state, as captured by a save and restored by a load.
+///
+/// The default value is the state of a new game, and keys missing from the maps fall back to it.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+#[serde(default)]
+pub struct SaveState {
+    pub resources: BTreeMap<ResourceKind, ResourceState>,
+    pub buildings: BTreeMap<BuildingKind, BuildingState>,
+    pub calendar: CalendarState,
+    pub unlocked_recipes: BTreeSet<RecipeKind>,
+    pub visible_nodes: BTreeSet<NodeId>,
+    /// Wall-clock time of the save in milliseconds since the Unix epoch, used to catch up on the
+    /// time that passed until it is loaded.
+    pub saved_at: Option<i64>,
+    /// Time spent with the simulation running, across all sessions.
+    pub play_time_seconds: f64,
+}
+
+impl Default for SaveState {
+    fn default() -> Self {
+        Self {
+            resources: Default::default(),
+            buildings: Default::default(),
+            calendar: Default::default(),
+            unlocked_recipes: Default::default(),
+            visible_nodes: NODE_VISIBILITY
+                .iter()
+                .filter_map(|(node, is_visible)| is_visible.then_some(*node))
+                .collect(),
+            saved_at: None,
+            play_time_seconds: 0.0,
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(default)]
+pub struct ResourceState {
+    pub amount: f64,
+    pub unlocked: bool,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[serde(default)]
+pub struct BuildingState {
+    pub level: u32,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CalendarState {
+    pub day: i16,
+    pub season: SeasonKind,
+    pub year: usize,
+}
+
+impl Default for CalendarState {
+    fn default() -> Self {
+        Self {
+            day: 0,
+            season: SeasonKind::Spring,
+            year: 0,
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use serde::{Deserialize, Serialize};
+
+use crate::{communication::Intent, state::time::RunningState};
+
+use super::{LoadError, SavePayload};
+
+/// Everything that fed the simulation since the last load, so that the same run can be played
+/// back and end in exactly the same state.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct Replay {
+    /// State the recording started from, or `None` for the world of a freshly started engine.
+    pub initial: Option<SavePayload>,
+    pub running_state: RunningState,
+    pub speed: f64,
+    pub entries: Vec<ReplayEntry>,
+    /// Number of base ticks that ran from the start of the recording until it was taken.
+    pub ticks: u64,
+    /// State at the end of the recording, without its save timestamp.
+    pub outcome: SavePayload,
+}
+
+/// An input to the simulation, tagged with the number of base ticks that had run when it arrived.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ReplayEntry {
+    pub tick: u64,
+    pub input: ReplayInput,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub enum ReplayInput {
+    Intent(Intent),
+    /// Wall-clock time spent suspended or saved, which the simulation catches up on.
+    TimeAway {
+        gap_millis: i64,
+    },
+}
+
+impl Replay {
+    pub fn encode(&self) -> String {
+        serde_json::to_string(self).expect("replay should always serialize")
+    }
+
+    pub fn decode(text: &str) -> Result<Self, LoadError> {
+        serde_json::from_str(text).map_err(|e| LoadError::Malformed(e.to_string()))
+    }
+}
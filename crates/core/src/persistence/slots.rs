@@ -0,0 +1,15 @@
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SlotId(pub u32);
+
+/// Summary of a save slot, shown when picking which game to continue.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SlotMetadata {
+    pub id: SlotId,
+    pub name: String,
+    /// Wall-clock time of the last save in milliseconds since the Unix epoch.
+    pub last_played: i64,
+    pub year: usize,
+    pub play_time_seconds: f64,
+}
@@ -1,5 +1,10 @@
+use std::ops::RangeInclusive;
+
 use serde::{Deserialize, Serialize};
 
+/// Days of a season, after the last of which the next season starts.
+pub const SEASON_DAYS: RangeInclusive<i16> = 0..=99;
+
 #[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 pub enum SeasonKind {
     Spring,
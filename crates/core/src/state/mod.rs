@@ -14,7 +14,7 @@ pub trait KeyIter {
 
 #[macro_export]
 macro_rules! state_key {
-    { $vis:vis enum $ident:ident $tt:tt } => {
+    { $vis:vis enum $ident:ident { $($(#[$meta:meta])* $variant:ident),* $(,)? } } => {
         #[derive(
             ::std::fmt::Debug,
             ::serde::Serialize,
@@ -28,7 +28,7 @@ macro_rules! state_key {
             ::core::marker::Copy,
             ::strum::EnumIter,
         )]
-        $vis enum $ident $tt
+        $vis enum $ident { $($(#[$meta])* $variant),* }
 
         impl $crate::state::KeyIter for $ident {
             type Item = $ident;
@@ -37,5 +37,12 @@ macro_rules! state_key {
                 <$ident as ::strum::IntoEnumIterator>::iter()
             }
         }
+
+        impl $crate::communication::Schema for $ident {
+            const FINGERPRINT: u64 = $crate::communication::fingerprint(
+                stringify!(enum $ident { $($variant),* }),
+                &[],
+            );
+        }
     };
 }
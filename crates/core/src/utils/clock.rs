@@ -0,0 +1,6 @@
+use time::OffsetDateTime;
+
+/// Current wall-clock time in milliseconds since the Unix epoch.
+pub fn unix_millis() -> i64 {
+    (OffsetDateTime::now_utc().unix_timestamp_nanos() / 1_000_000) as i64
+}
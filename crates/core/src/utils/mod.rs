@@ -1,3 +1,5 @@
+mod clock;
 mod shared;
 
+pub use clock::*;
 pub use shared::*;
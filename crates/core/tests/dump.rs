@@ -0,0 +1,146 @@
+use sorrow_core::{
+    persistence::{dump, SaveState},
+    state::calendar::SeasonKind,
+};
+
+/// Paths as they start in the table of the module docs.
+const DOCUMENTED_PATHS: [&str; 7] = [
+    "resource.",
+    "building.",
+    "calendar.",
+    "recipe.",
+    "visible.",
+    "play_time",
+    "saved_at",
+];
+
+/// A value for the field at `path` that differs from `current`.
+fn other_value(path: &str, current: &str) -> String {
+    match current {
+        "true" => return "false".to_string(),
+        "false" => return "true".to_string(),
+        _ => {}
+    }
+    let value = match path {
+        "calendar.day" => "42",
+        "calendar.season" => "winter",
+        "calendar.year" => "7",
+        "play_time" => "90.5",
+        "saved_at" => "1700000000000",
+        path if path.starts_with("resource.") => "12.5",
+        path if path.starts_with("building.") => "3",
+        _ => panic!("no value to set {path} to"),
+    };
+    value.to_string()
+}
+
+#[test]
+fn lists_every_documented_field() {
+    let fields = dump::fields(&SaveState::default());
+    for prefix in DOCUMENTED_PATHS {
+        assert!(
+            fields.keys().any(|path| path.starts_with(prefix)),
+            "no field starts with {prefix}"
+        );
+    }
+    assert!(fields.contains_key("resource.catnip"));
+    assert!(fields.contains_key("resource.catnip.unlocked"));
+    assert!(fields.contains_key("building.catnip_field"));
+    assert!(fields.contains_key("recipe.crafting.refine_catnip"));
+}
+
+#[test]
+fn sets_every_field_it_lists() {
+    let original = dump::fields(&SaveState::default());
+    for (path, current) in &original {
+        let value = other_value(path, current);
+        let mut state = SaveState::default();
+        dump::set(&mut state, path, &value).unwrap_or_else(|error| panic!("{error}"));
+
+        let mut expected = original.clone();
+        expected.insert(path.clone(), value);
+        assert_eq!(dump::fields(&state), expected, "setting {path}");
+    }
+}
+
+#[test]
+fn sets_values_into_the_state() {
+    let mut state = SaveState::default();
+    dump::set(&mut state, "calendar.season", "autumn").unwrap();
+    dump::set(&mut state, "calendar.day", "99").unwrap();
+    dump::set(&mut state, "saved_at", "1700000000000").unwrap();
+    assert_eq!(state.calendar.season, SeasonKind::Autumn);
+    assert_eq!(state.calendar.day, 99);
+    assert_eq!(state.saved_at, Some(1_700_000_000_000));
+
+    dump::set(&mut state, "saved_at", "none").unwrap();
+    assert_eq!(state.saved_at, None);
+}
+
+#[test]
+fn refuses_unknown_fields() {
+    let mut state = SaveState::default();
+    for path in [
+        "resource.gold",
+        "building.barn",
+        "calendar.month",
+        "recipe.crafting",
+        "visible.bonfire",
+        "play_time.total",
+        "nonsense",
+        "",
+    ] {
+        let error = dump::set(&mut state, path, "1").unwrap_err();
+        assert_eq!(error, format!("unknown field {path}"));
+    }
+    assert_eq!(state, SaveState::default());
+}
+
+#[test]
+fn refuses_values_that_do_not_parse() {
+    let mut state = SaveState::default();
+    for (path, value) in [
+        ("resource.catnip", "lots"),
+        ("resource.catnip.unlocked", "yes"),
+        ("building.catnip_field", "-1"),
+        ("building.catnip_field", "1.5"),
+        ("calendar.year", "soon"),
+        ("recipe.crafting.refine_catnip", "1"),
+        ("saved_at", "yesterday"),
+    ] {
+        let error = dump::set(&mut state, path, value).unwrap_err();
+        assert_eq!(error, format!("{path} cannot be set to {value}"));
+    }
+    assert_eq!(state, SaveState::default());
+}
+
+#[test]
+fn refuses_unknown_seasons() {
+    let mut state = SaveState::default();
+    for season in ["monsoon", "Spring", ""] {
+        let error = dump::set(&mut state, "calendar.season", season).unwrap_err();
+        assert!(error.contains("needs a season"), "{error}");
+    }
+}
+
+#[test]
+fn refuses_days_outside_of_a_season() {
+    let mut state = SaveState::default();
+    for day in ["100", "-1"] {
+        let error = dump::set(&mut state, "calendar.day", day).unwrap_err();
+        assert!(error.contains("needs a day"), "{error}");
+    }
+    assert_eq!(state.calendar.day, 0);
+}
+
+#[test]
+fn refuses_negative_or_unbounded_quantities() {
+    let mut state = SaveState::default();
+    for path in ["resource.catnip", "play_time"] {
+        for value in ["-1", "NaN", "inf", "-inf"] {
+            let error = dump::set(&mut state, path, value).unwrap_err();
+            assert!(error.contains("needs a finite amount"), "{error}");
+        }
+    }
+    assert_eq!(state, SaveState::default());
+}
@@ -0,0 +1,66 @@
+use sorrow_core::{
+    persistence::{kittens, LoadError},
+    state::{
+        buildings::BuildingKind, calendar::SeasonKind, recipes::RecipeKind,
+        resources::ResourceKind, ui::NodeId,
+    },
+};
+
+/// A trimmed Kittens Game save, compressed with the algorithm of `LZString.compressToBase64`.
+///
+/// The game name holds characters outside of Latin-1, which are stored as 16-bit units.
+const EXPORT: &str = include_str!("fixtures/kittens_export.txt");
+
+#[test]
+fn imports_a_kittens_game_export() {
+    let import = kittens::import(EXPORT).unwrap();
+    let state = import.state;
+
+    let catnip = state.resources[&ResourceKind::Catnip];
+    assert_eq!(catnip.amount, 1234.5);
+    assert!(catnip.unlocked);
+    let wood = state.resources[&ResourceKind::Wood];
+    assert_eq!(wood.amount, 0.0);
+    assert!(!wood.unlocked);
+
+    assert_eq!(state.buildings[&BuildingKind::CatnipField].level, 3);
+    let recipe = RecipeKind::Building(BuildingKind::CatnipField);
+    assert!(state.unlocked_recipes.contains(&recipe));
+    assert!(state.visible_nodes.contains(&NodeId::from(recipe)));
+    assert!(state
+        .visible_nodes
+        .contains(&NodeId::from(ResourceKind::Catnip)));
+
+    // Day 150.7 is past the end of a season here, so it is clamped to the last day.
+    assert_eq!(state.calendar.day, 99);
+    assert_eq!(state.calendar.season, SeasonKind::Autumn);
+    assert_eq!(state.calendar.year, 5);
+
+    // Only entries that were in use are reported.
+    assert_eq!(import.unmapped.resources, ["minerals"]);
+    assert_eq!(import.unmapped.buildings, ["hut"]);
+}
+
+#[test]
+fn ignores_whitespace_in_the_export() {
+    let wrapped = EXPORT
+        .as_bytes()
+        .chunks(64)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_eq!(kittens::import(&wrapped), kittens::import(EXPORT));
+}
+
+#[test]
+fn refuses_text_that_is_not_an_export() {
+    assert!(matches!(
+        kittens::import("not an export!"),
+        Err(LoadError::Corrupted(_))
+    ));
+    assert!(matches!(
+        kittens::import(&EXPORT[..EXPORT.len() / 2]),
+        Err(LoadError::Corrupted(_) | LoadError::Malformed(_))
+    ));
+}
@@ -0,0 +1,53 @@
+use serde_json::json;
+
+use sorrow_core::persistence::{LoadError, SavePayload, SaveState, SCHEMA_VERSION};
+
+#[test]
+fn decodes_a_payload_of_the_current_version() {
+    let mut state = SaveState {
+        play_time_seconds: 12.5,
+        ..Default::default()
+    };
+    state.calendar.day = 3;
+
+    assert_eq!(SavePayload::encode(&state).decode(), Ok(state));
+}
+
+#[test]
+fn fills_in_missing_keys_with_new_game_values() {
+    let payload = SavePayload(json!({ "version": SCHEMA_VERSION, "state": {} }).to_string());
+
+    assert_eq!(payload.decode(), Ok(SaveState::default()));
+}
+
+#[test]
+fn refuses_a_payload_from_a_newer_version() {
+    let payload = SavePayload(json!({ "version": SCHEMA_VERSION + 1, "state": {} }).to_string());
+
+    assert_eq!(
+        payload.decode(),
+        Err(LoadError::NewerVersion {
+            version: SCHEMA_VERSION + 1,
+            supported: SCHEMA_VERSION,
+        })
+    );
+}
+
+#[test]
+fn refuses_a_payload_that_is_not_a_save() {
+    assert!(matches!(
+        SavePayload("not a save".to_string()).decode(),
+        Err(LoadError::Malformed(_))
+    ));
+}
+
+#[test]
+fn runs_the_migration_chain_for_older_payloads() {
+    // No migration upgrades from before the first version, so the chain has to stop there.
+    let payload = SavePayload(json!({ "version": 0, "state": {} }).to_string());
+
+    assert!(matches!(
+        payload.decode(),
+        Err(LoadError::Migration { from: 0, .. })
+    ));
+}
@@ -0,0 +1,235 @@
+//! A heuristic player for measuring how long the game takes to reach its milestones.
+//!
+//! The bot only sees what a client would, the [`EngineMessage`]s, and only acts through
+//! [`Intent`]s. On every tick it gathers catnip, builds a Catnip Field as soon as one is
+//! fulfilled, and once it has [`REFINE_AFTER_FIELDS`] fields it refines catnip whenever it cannot
+//! afford another field. Runs use a manual clock and a fresh game, so the same build of the game
+//! always produces the same [`Report`].
+
+use std::{collections::BTreeMap, fmt};
+
+use sorrow_core::{
+    communication::{EngineMessage, EngineUpdate, Intent, WorkOrderKind},
+    state::{
+        buildings::BuildingKind,
+        recipes::{CraftingRecipeKind, FulfillmentState, RecipeKind},
+        resources::ResourceKind,
+    },
+};
+
+use crate::{persistence::storage::MemoryStorage, runner::HeadlessRunnerPlugin, HeadlessEngine};
+
+/// Fields to build before any catnip is spent on refining.
+pub const REFINE_AFTER_FIELDS: u32 = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Milestone {
+    FirstField,
+    TenFields,
+    FirstWood,
+    CapacityReached,
+}
+
+impl Milestone {
+    const ALL: [Milestone; 4] = [
+        Milestone::FirstField,
+        Milestone::TenFields,
+        Milestone::FirstWood,
+        Milestone::CapacityReached,
+    ];
+
+    fn label(self) -> &'static str {
+        match self {
+            Milestone::FirstField => "first field",
+            Milestone::TenFields => "10 fields",
+            Milestone::FirstWood => "first wood",
+            Milestone::CapacityReached => "capacity reached",
+        }
+    }
+}
+
+/// Ticks it took to reach each milestone, and the state of the game at the end of the run.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub ticks: u64,
+    pub reached: BTreeMap<Milestone, u64>,
+    /// The resource that reached its capacity first.
+    pub capped_resource: Option<ResourceKind>,
+    pub fields: u32,
+    pub catnip: f64,
+    pub wood: f64,
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{:<20}{:>10}", "milestone", "tick")?;
+        for milestone in Milestone::ALL {
+            match self.reached.get(&milestone) {
+                Some(tick) => writeln!(f, "{:<20}{tick:>10}", milestone.label())?,
+                None => writeln!(f, "{:<20}{:>10}", milestone.label(), "-")?,
+            }
+        }
+        if let Some(resource) = self.capped_resource {
+            writeln!(f, "capped resource: {resource:?}")?;
+        }
+        writeln!(f, "after {} ticks:", self.ticks)?;
+        writeln!(f, "  fields: {}", self.fields)?;
+        writeln!(f, "  catnip: {:.3}", self.catnip)?;
+        write!(f, "  wood: {:.3}", self.wood)
+    }
+}
+
+/// What the bot knows about the game, built from the updates it received.
+#[derive(Debug, Default)]
+struct View {
+    amounts: BTreeMap<ResourceKind, f64>,
+    capacities: BTreeMap<ResourceKind, f64>,
+    levels: BTreeMap<BuildingKind, u32>,
+    fulfillments: BTreeMap<RecipeKind, FulfillmentState>,
+}
+
+impl View {
+    fn apply(&mut self, update: &EngineUpdate) {
+        match update {
+            EngineUpdate::ResourcesChanged(resources) => {
+                for (kind, amount) in resources.amounts.iter() {
+                    if let Some(amount) = amount {
+                        self.amounts.insert(*kind, *amount);
+                    }
+                }
+                for (kind, capacity) in resources.capacities.iter() {
+                    match capacity {
+                        Some(Some(capacity)) => self.capacities.insert(*kind, *capacity),
+                        Some(None) => self.capacities.remove(kind),
+                        None => None,
+                    };
+                }
+            }
+            EngineUpdate::BuildingsChanged(buildings) => {
+                for (kind, level) in buildings.levels.iter() {
+                    if let Some(level) = level {
+                        self.levels.insert(*kind, *level);
+                    }
+                }
+            }
+            EngineUpdate::FulfillmentsChanged(fulfillments) => {
+                for (kind, fulfillment) in fulfillments.fulfillments.iter() {
+                    if let Some(fulfillment) = fulfillment {
+                        self.fulfillments.insert(*kind, *fulfillment);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn amount(&self, kind: ResourceKind) -> f64 {
+        self.amounts.get(&kind).copied().unwrap_or_default()
+    }
+
+    fn level(&self, kind: BuildingKind) -> u32 {
+        self.levels.get(&kind).copied().unwrap_or_default()
+    }
+
+    fn is_fulfilled(&self, kind: RecipeKind) -> bool {
+        matches!(
+            self.fulfillments.get(&kind),
+            Some(FulfillmentState::Fulfilled)
+        )
+    }
+
+    fn capped_resource(&self) -> Option<ResourceKind> {
+        self.capacities
+            .iter()
+            .find(|(kind, capacity)| self.amount(**kind) >= **capacity)
+            .map(|(kind, _)| *kind)
+    }
+}
+
+/// Plays a new game for at most `max_ticks`, stopping early once every milestone is reached.
+pub fn run(max_ticks: u64) -> Report {
+    let mut engine = HeadlessEngine::new(
+        HeadlessRunnerPlugin::one_tick_per_update(),
+        MemoryStorage::default(),
+    );
+    let mut view = View::default();
+    let mut report = Report {
+        ticks: 0,
+        reached: BTreeMap::new(),
+        capped_resource: None,
+        fields: 0,
+        catnip: 0.0,
+        wood: 0.0,
+    };
+
+    observe(&mut engine, &mut view);
+    for tick in 1..=max_ticks {
+        for intent in decide(&view) {
+            engine.send(intent);
+        }
+        engine.update();
+        observe(&mut engine, &mut view);
+
+        report.ticks = tick;
+        record_milestones(&view, tick, &mut report);
+        if report.reached.len() == Milestone::ALL.len() {
+            break;
+        }
+    }
+
+    report.fields = view.level(BuildingKind::CatnipField);
+    report.catnip = view.amount(ResourceKind::Catnip);
+    report.wood = view.amount(ResourceKind::Wood);
+    report
+}
+
+fn observe(engine: &mut HeadlessEngine, view: &mut View) {
+    for message in engine.receive() {
+        if let EngineMessage::Updated(updates) = message {
+            for update in &updates {
+                view.apply(update);
+            }
+        }
+    }
+}
+
+fn decide(view: &View) -> Vec<Intent> {
+    let mut intents = vec![craft(CraftingRecipeKind::GatherCatnip)];
+
+    let field = RecipeKind::Building(BuildingKind::CatnipField);
+    let refine = RecipeKind::Crafting(CraftingRecipeKind::RefineCatnip);
+    if view.is_fulfilled(field) {
+        intents.push(Intent::QueueWorkOrder(WorkOrderKind::Construct(
+            BuildingKind::CatnipField,
+        )));
+    } else if view.level(BuildingKind::CatnipField) >= REFINE_AFTER_FIELDS
+        && view.is_fulfilled(refine)
+    {
+        intents.push(craft(CraftingRecipeKind::RefineCatnip));
+    }
+
+    intents
+}
+
+fn craft(recipe: CraftingRecipeKind) -> Intent {
+    Intent::QueueWorkOrder(WorkOrderKind::Craft(recipe))
+}
+
+fn record_milestones(view: &View, tick: u64, report: &mut Report) {
+    let fields = view.level(BuildingKind::CatnipField);
+    let capped = view.capped_resource();
+
+    let mut reach = |milestone, condition: bool| {
+        if condition {
+            report.reached.entry(milestone).or_insert(tick);
+        }
+    };
+    reach(Milestone::FirstField, fields >= 1);
+    reach(Milestone::TenFields, fields >= 10);
+    reach(Milestone::FirstWood, view.amount(ResourceKind::Wood) > 0.0);
+    reach(Milestone::CapacityReached, capped.is_some());
+
+    if report.capped_resource.is_none() {
+        report.capped_resource = capped;
+    }
+}
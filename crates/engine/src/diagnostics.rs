@@ -0,0 +1,198 @@
+//! Collection of recent activity for [`DiagnosticBundle`]s attached to bug reports.
+
+use std::{
+    collections::VecDeque,
+    fmt::{Debug, Write},
+    sync::{Arc, Mutex},
+};
+
+use bevy::{
+    app::{App, First, Last, Plugin},
+    ecs::system::SystemParam,
+    log::{
+        tracing_subscriber::{layer::Context, Layer},
+        BoxedLayer,
+    },
+    prelude::*,
+};
+use tracing::{
+    field::{Field, Visit},
+    Subscriber,
+};
+
+use sorrow_core::{
+    communication::EngineMessage,
+    diagnostics::{DiagnosticBundle, EngineSettings},
+    persistence::{SavePayload, SCHEMA_VERSION},
+    utils::unix_millis,
+};
+
+use crate::{
+    io::{self, OutputEvent},
+    persistence::{
+        self, export, slots::Slots, AutosaveInterval, PendingSave, SaveRequests, SaveTarget,
+    },
+    replay::Recording,
+    runner::FramePacing,
+    simulation::{ticker::TickRate, time::TimeState},
+};
+
+const MAX_INTENTS: usize = 200;
+const MAX_MESSAGES: usize = 50;
+const MAX_LOG_LINES: usize = 500;
+
+/// The most recent lines of log output, shared with the tracing layer that writes them.
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LogBuffer(Arc<Mutex<VecDeque<String>>>);
+
+impl LogBuffer {
+    fn push(&self, line: String) {
+        let Ok(mut lines) = self.0.lock() else {
+            return;
+        };
+        if lines.len() == MAX_LOG_LINES {
+            lines.pop_front();
+        }
+        lines.push_back(line);
+    }
+
+    fn lines(&self) -> Vec<String> {
+        self.0
+            .lock()
+            .map(|lines| lines.iter().cloned().collect())
+            .unwrap_or_default()
+    }
+}
+
+/// Keeps the log output in a [`LogBuffer`], for use as the custom layer of the `LogPlugin`.
+pub fn log_layer(app: &mut App) -> Option<BoxedLayer> {
+    let buffer = LogBuffer::default();
+    app.insert_resource(buffer.clone());
+    Some(Box::new(BufferLayer(buffer)))
+}
+
+struct BufferLayer(LogBuffer);
+
+impl<S: Subscriber> Layer<S> for BufferLayer {
+    fn on_event(&self, event: &tracing::Event<'_>, _ctx: Context<'_, S>) {
+        let metadata = event.metadata();
+        let mut line = format!(
+            "{} {} {}:",
+            unix_millis(),
+            metadata.level(),
+            metadata.target()
+        );
+        event.record(&mut LineVisitor(&mut line));
+        self.0.push(line);
+    }
+}
+
+struct LineVisitor<'a>(&'a mut String);
+
+impl Visit for LineVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        let _ = match field.name() {
+            "message" => write!(self.0, " {value:?}"),
+            name => write!(self.0, " {name}={value:?}"),
+        };
+    }
+}
+
+/// The most recent messages sent to the client, as JSON.
+#[derive(Resource, Debug, Default)]
+struct RecentMessages(VecDeque<String>);
+
+pub struct DiagnosticsPlugin;
+
+impl Plugin for DiagnosticsPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RecentMessages>()
+            .add_systems(
+                First,
+                export_diagnostics
+                    .after(persistence::sets::Save)
+                    .before(persistence::sets::Complete),
+            )
+            .add_systems(
+                Last,
+                record_messages
+                    .after(io::batch_updates)
+                    .before(io::sets::Outputs),
+            );
+    }
+}
+
+fn record_messages(mut outputs: EventReader<OutputEvent>, mut recent: ResMut<RecentMessages>) {
+    for OutputEvent(message) in outputs.read() {
+        match message {
+            // Frames where nothing changed, snapshots and earlier bundles only crowd out what
+            // matters.
+            EngineMessage::Updated(updates) if updates.is_empty() => continue,
+            EngineMessage::Snapshot(_) | EngineMessage::Diagnostics(_) => continue,
+            _ => {}
+        }
+
+        match serde_json::to_string(message) {
+            Ok(json) => {
+                if recent.0.len() == MAX_MESSAGES {
+                    recent.0.pop_front();
+                }
+                recent.0.push_back(json);
+            }
+            Err(error) => tracing::warn!("Could not keep a message for diagnostics: {error}"),
+        }
+    }
+}
+
+#[derive(SystemParam)]
+struct Settings<'w> {
+    tick_rate: Res<'w, TickRate>,
+    autosave_interval: Res<'w, AutosaveInterval>,
+    time_state: Res<'w, TimeState>,
+    slots: Res<'w, Slots>,
+    pacing: Option<Res<'w, FramePacing>>,
+}
+
+impl Settings<'_> {
+    fn collect(&self) -> EngineSettings {
+        EngineSettings {
+            seconds_per_tick: self.tick_rate.seconds_per_tick,
+            autosave_interval: self.autosave_interval.0,
+            running_state: self.time_state.running_state,
+            speed: self.time_state.speed,
+            active_slot: self.slots.active().cloned(),
+            frame: self.pacing.as_ref().map(|pacing| pacing.timing()),
+        }
+    }
+}
+
+fn export_diagnostics(
+    requests: Res<SaveRequests>,
+    save: Res<PendingSave>,
+    settings: Settings,
+    recording: Res<Recording>,
+    messages: Res<RecentMessages>,
+    log: Option<Res<LogBuffer>>,
+    mut outputs: EventWriter<OutputEvent>,
+) {
+    if !requests.contains(SaveTarget::Diagnostics) {
+        return;
+    }
+
+    let bundle = DiagnosticBundle {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        schema_version: SCHEMA_VERSION,
+        created_at: unix_millis(),
+        settings: settings.collect(),
+        state: SavePayload::encode(&save.0),
+        intents: recording.recent(MAX_INTENTS).to_vec(),
+        messages: messages.0.iter().cloned().collect(),
+        log: log.map(|log| log.lines()).unwrap_or_default(),
+    };
+    match serde_json::to_string(&bundle) {
+        Ok(json) => {
+            outputs.send(EngineMessage::Diagnostics(export::pack(&json)).into());
+        }
+        Err(error) => tracing::error!("Could not serialize diagnostics: {error}"),
+    }
+}
@@ -1,17 +1,50 @@
 use sorrow_worker::{Spawnable, WorkerBridge};
 
+pub use sorrow_worker::CodecError;
+
 use sorrow_core::communication::{EngineMessage, Intent};
 
-use super::io::Worker;
+use super::io::{Encoding, Worker};
 
 pub struct Endpoint(WorkerBridge<Worker>);
 
 impl Endpoint {
-    pub fn new<F>(cb: F, path: &str) -> Self
+    /// Starts the engine in a web worker loaded from `path`.
+    ///
+    /// Messages that either side could not encode or decode go to `on_error`, for this connection
+    /// and every one forked from it.
+    pub fn new<F, E>(cb: F, on_error: E, path: &str) -> Self
+    where
+        F: 'static + Fn(EngineMessage),
+        E: 'static + Fn(CodecError),
+    {
+        Self(
+            Worker::spawner()
+                .encoding::<Encoding>()
+                .callback(cb)
+                .error_callback(on_error)
+                .spawn(path),
+        )
+    }
+
+    /// Opens another connection to the same engine, with its own callback.
+    ///
+    /// Updates are sent to every connection, replies only to the connection that asked.
+    pub fn fork<F>(&self, cb: F) -> Self
+    where
+        F: 'static + Fn(EngineMessage),
+    {
+        Self(self.0.fork(Some(cb)))
+    }
+
+    /// Opens a connection to the same engine that can watch the game, but not change it.
+    pub fn spectate<F>(&self, cb: F) -> Self
     where
         F: 'static + Fn(EngineMessage),
     {
-        Self(Worker::spawner().callback(cb).spawn(path))
+        let endpoint = self.fork(cb);
+        endpoint.send(Intent::Spectate);
+        endpoint
     }
 
     pub fn send(&self, command: Intent) {
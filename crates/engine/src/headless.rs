@@ -0,0 +1,84 @@
+use bevy::app::{App, AppExit};
+use bevy::ecs::world::World;
+
+use sorrow_core::communication::{EngineMessage, Intent};
+
+use crate::{
+    io::{Channel, ChannelPlugin},
+    persistence::{storage::SaveStorage, PendingFlush},
+    runner::HeadlessRunnerPlugin,
+    EnginePlugins,
+};
+
+/// Frames to wait for the storage to take the last save before giving up on a shutdown.
+const MAX_SHUTDOWN_FRAMES: usize = 100;
+
+/// The full engine running natively, with intents and messages passed through a [`Channel`].
+pub struct HeadlessEngine {
+    app: App,
+}
+
+impl HeadlessEngine {
+    pub fn new(runner: HeadlessRunnerPlugin, storage: impl SaveStorage + 'static) -> Self {
+        let mut app = App::new();
+        app.add_plugins(runner)
+            .add_plugins(EnginePlugins {
+                storage: Box::new(storage),
+            })
+            .add_plugins(ChannelPlugin);
+
+        app.finish();
+        app.cleanup();
+
+        // The first frame runs startup and starts the clock, so later frames advance by a full step.
+        app.update();
+
+        Self { app }
+    }
+
+    pub fn send(&mut self, intent: Intent) {
+        self.channel().send(intent);
+    }
+
+    /// Runs a single frame.
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    /// Takes the messages sent since the last call.
+    pub fn receive(&mut self) -> Vec<EngineMessage> {
+        self.channel().receive()
+    }
+
+    /// Shuts down like closing the worker would, running frames until the last save is written.
+    ///
+    /// Returns `false` if the storage did not confirm the last save in time.
+    pub fn shutdown(&mut self) -> bool {
+        self.channel().request_shutdown();
+        for _ in 0..MAX_SHUTDOWN_FRAMES {
+            self.update();
+            if !self.world().contains_resource::<PendingFlush>() {
+                return true;
+            }
+        }
+        tracing::warn!("Gave up waiting for the last save to be written");
+        false
+    }
+
+    /// Hands the app over to the runner's loop until it exits.
+    pub fn run(mut self) -> AppExit {
+        self.app.run()
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    fn channel(&mut self) -> bevy::prelude::Mut<'_, Channel> {
+        self.app.world_mut().resource_mut::<Channel>()
+    }
+}
@@ -114,10 +114,7 @@ where
         self.lookup
             .inner
             .values()
-            .filter_map(|v| match self.query.get(*v) {
-                Ok(value) => Some(value),
-                _ => None,
-            })
+            .filter_map(|v| self.query.get(*v).ok())
     }
 }
 
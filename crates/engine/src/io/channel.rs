@@ -0,0 +1,58 @@
+use bevy::{
+    app::{First, Last, Plugin},
+    prelude::{EventWriter, Events, IntoSystemConfigs, ResMut, Resource},
+};
+use sorrow_core::communication::{EngineMessage, Intent};
+
+use super::{sets, InputEvent, OutputEvent, ShutdownEvent};
+
+/// In-memory replacement for the worker dispatcher, for running without a browser.
+///
+/// Intents queued with [`Channel::send`] are read at the start of the next frame, and the messages
+/// sent during a frame can be taken with [`Channel::receive`] once it is over.
+#[derive(Resource, Debug, Default)]
+pub struct Channel {
+    inputs: Vec<Intent>,
+    outputs: Vec<EngineMessage>,
+    shutdown_requested: bool,
+}
+
+impl Channel {
+    pub fn send(&mut self, intent: Intent) {
+        self.inputs.push(intent);
+    }
+
+    pub fn receive(&mut self) -> Vec<EngineMessage> {
+        std::mem::take(&mut self.outputs)
+    }
+
+    /// Announces a shutdown at the start of the next frame, like closing the worker would.
+    pub fn request_shutdown(&mut self) {
+        self.shutdown_requested = true;
+    }
+}
+
+pub struct ChannelPlugin;
+
+impl Plugin for ChannelPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.init_resource::<Channel>()
+            .add_systems(First, receive_inputs.in_set(sets::Inputs))
+            .add_systems(Last, send_outputs.in_set(sets::Outputs));
+    }
+}
+
+fn receive_inputs(
+    mut inputs: EventWriter<InputEvent>,
+    mut shutdowns: EventWriter<ShutdownEvent>,
+    mut channel: ResMut<Channel>,
+) {
+    inputs.send_batch(channel.inputs.drain(..).map(InputEvent));
+    if std::mem::take(&mut channel.shutdown_requested) {
+        shutdowns.send(ShutdownEvent);
+    }
+}
+
+fn send_outputs(mut outputs: ResMut<Events<OutputEvent>>, mut channel: ResMut<Channel>) {
+    channel.outputs.extend(outputs.drain().map(|e| e.0));
+}
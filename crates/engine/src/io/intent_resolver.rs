@@ -1,16 +1,26 @@
 use bevy::{
     app::{First, Plugin},
-    prelude::{EventReader, EventWriter, IntoSystemConfigs},
+    ecs::system::SystemParam,
+    prelude::{Commands, EventReader, EventWriter, IntoSystemConfigs, ResMut},
 };
 
 use sorrow_core::{
-    communication::{EngineMessage, EngineUpdate, Intent, TimeControl, TimeTransport},
+    communication::{EngineMessage, HandshakeReply, Intent, ProtocolInfo, TimeControl},
     state::time::RunningState,
 };
 
-use crate::simulation::work_orders::WorkOrder;
+use crate::{
+    persistence::{
+        export, rewind::RewindRequest, slots::SlotRequest, PendingLoad, SaveRequests, SaveTarget,
+    },
+    runner::FramePacing,
+    simulation::{
+        time::{self, TimeState},
+        work_orders::WorkOrder,
+    },
+};
 
-use super::{InputEvent, OutputEvent, UpdatedEvent};
+use super::{snapshot::PendingSnapshot, InputEvent, OutputEvent};
 
 pub mod sets {
     use bevy::prelude::SystemSet;
@@ -27,40 +37,129 @@ impl Plugin for IntentResolverPlugin {
     }
 }
 
+/// Requests handed on to the domain systems that carry them out.
+#[derive(SystemParam)]
+struct Requests<'w> {
+    work_orders: EventWriter<'w, WorkOrder>,
+    slots: EventWriter<'w, SlotRequest>,
+    rewinds: EventWriter<'w, RewindRequest>,
+}
+
 fn resolve_intents(
+    mut cmd: Commands,
     mut inputs: EventReader<InputEvent>,
-    mut work_orders: EventWriter<WorkOrder>,
+    mut requests: Requests,
     mut outputs: EventWriter<OutputEvent>,
-    mut updates: EventWriter<UpdatedEvent>,
+    mut time_state: ResMut<TimeState>,
+    mut save_requests: ResMut<SaveRequests>,
+    mut pacing: Option<ResMut<FramePacing>>,
 ) {
     for InputEvent(message) in inputs.read() {
         match message {
-            Intent::Load => {
-                outputs.send(OutputEvent(EngineMessage::Loaded));
+            Intent::Handshake(client) => {
+                let engine = ProtocolInfo::current();
+                let compatible = engine.is_compatible_with(client);
+                if !compatible {
+                    tracing::warn!("Client speaks {client:?}, which differs from {engine:?}");
+                }
+                outputs
+                    .send(EngineMessage::Handshake(HandshakeReply { engine, compatible }).into());
+            }
+            Intent::Load(payload) => match payload.decode() {
+                Ok(state) => cmd.insert_resource(PendingLoad(state)),
+                Err(error) => {
+                    tracing::warn!("Could not load save: {error}");
+                    outputs.send(EngineMessage::LoadFailed(error).into());
+                }
+            },
+            Intent::Save => {
+                save_requests.request(SaveTarget::Client);
+            }
+            Intent::Export => {
+                save_requests.request(SaveTarget::Export);
+            }
+            Intent::ExportReplay => {
+                save_requests.request(SaveTarget::Replay);
             }
+            Intent::ExportDiagnostics => {
+                save_requests.request(SaveTarget::Diagnostics);
+            }
+            Intent::Import(text) => match export::decode(text).and_then(|p| p.decode()) {
+                Ok(state) => cmd.insert_resource(PendingLoad(state)),
+                Err(error) => {
+                    tracing::warn!("Could not import save: {error}");
+                    outputs.send(EngineMessage::LoadFailed(error).into());
+                }
+            },
             Intent::QueueWorkOrder(kind) => {
-                work_orders.send(WorkOrder(*kind));
-            }
-            Intent::TimeControl(time_control) => {
-                match time_control {
-                    TimeControl::Pause => {
-                        updates.send(
-                            EngineUpdate::TimeChanged(TimeTransport {
-                                running_state: Some(RunningState::Paused),
-                            })
-                            .into(),
-                        );
-                    }
-                    TimeControl::Start => {
-                        updates.send(
-                            EngineUpdate::TimeChanged(TimeTransport {
-                                running_state: Some(RunningState::Running),
-                            })
-                            .into(),
-                        );
-                    }
-                };
+                requests.work_orders.send(WorkOrder(*kind));
+            }
+            Intent::ListSlots => {
+                requests.slots.send(SlotRequest::List);
+            }
+            Intent::CreateSlot(name) => {
+                requests.slots.send(SlotRequest::Create(name.clone()));
+            }
+            Intent::OpenSlot(id) => {
+                requests.slots.send(SlotRequest::Open(*id));
+            }
+            Intent::RenameSlot(id, name) => {
+                requests.slots.send(SlotRequest::Rename(*id, name.clone()));
+            }
+            Intent::DuplicateSlot(id, name) => {
+                requests
+                    .slots
+                    .send(SlotRequest::Duplicate(*id, name.clone()));
+            }
+            Intent::DeleteSlot(id) => {
+                requests.slots.send(SlotRequest::Delete(*id));
+            }
+            Intent::Rewind { days } => {
+                requests.rewinds.send(RewindRequest(*days));
+            }
+            Intent::PageHidden(hidden) => {
+                // Frames are only paced on the wall clock, a manual clock has no cadence to change.
+                if let Some(pacing) = pacing.as_mut() {
+                    pacing.hidden = *hidden;
+                }
+            }
+            Intent::Snapshot => {
+                cmd.init_resource::<PendingSnapshot>();
+            }
+            Intent::Spectate => {
+                // Spectators are told apart by the transport, which keeps this from reaching here.
+                tracing::warn!("Ignoring a request to spectate over a single connection");
+            }
+            Intent::TimeControl(TimeControl::Pause) => {
+                set_running_state(&mut time_state, RunningState::Paused);
+            }
+            Intent::TimeControl(TimeControl::Start) => {
+                set_running_state(&mut time_state, RunningState::Running);
+                time_state.pending_steps = 0;
+            }
+            Intent::TimeControl(TimeControl::Step(steps)) => {
+                if time_state.is_running() {
+                    tracing::warn!("Ignoring a step of {steps} ticks while time is running");
+                } else {
+                    time_state.pending_steps = time_state.pending_steps.saturating_add(*steps);
+                }
+            }
+            Intent::TimeControl(TimeControl::SetSpeed(speed)) => {
+                if !time::SPEED_RANGE.contains(speed) {
+                    tracing::warn!(
+                        "Ignoring game speed {speed} outside of {:?}",
+                        time::SPEED_RANGE
+                    );
+                } else if time_state.speed != *speed {
+                    time_state.speed = *speed;
+                }
             }
         };
     }
 }
+
+fn set_running_state(time_state: &mut ResMut<TimeState>, running_state: RunningState) {
+    if time_state.running_state != running_state {
+        time_state.running_state = running_state;
+    }
+}
@@ -1,18 +1,39 @@
+#[cfg(feature = "headless")]
+mod channel;
 mod intent_resolver;
+#[cfg(any(feature = "wasm", test))]
+mod routing;
+pub mod snapshot;
+#[cfg(feature = "wasm")]
 mod worker;
 
-use crate::schedules::SchedulesPlugin;
+use crate::{persistence, schedules::SchedulesPlugin};
 
-pub use self::worker::Worker;
+#[cfg(feature = "headless")]
+pub use self::channel::{Channel, ChannelPlugin};
+#[cfg(feature = "wasm")]
+pub use self::worker::{Encoding, Worker, WorkerPlugin};
 
 use bevy::{
-    app::{First, Plugin},
-    prelude::{Event, IntoSystemSetConfigs},
+    app::{First, Last, Plugin},
+    prelude::{Event, EventWriter, Events, IntoSystemConfigs, IntoSystemSetConfigs, ResMut},
 };
 
 use intent_resolver::IntentResolverPlugin;
+use snapshot::SnapshotPlugin;
 use sorrow_core::communication::{EngineMessage, EngineUpdate, Intent};
-use worker::WorkerPlugin;
+
+pub mod sets {
+    use bevy::prelude::SystemSet;
+
+    /// Intents from the client are turned into [`super::InputEvent`]s.
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Inputs;
+
+    /// [`super::OutputEvent`]s are handed over to the client.
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Outputs;
+}
 
 #[derive(Event)]
 pub struct InputEvent(pub Intent);
@@ -29,12 +50,23 @@ impl From<EngineUpdate> for UpdatedEvent {
 #[derive(Event)]
 pub struct OutputEvent(pub EngineMessage);
 
+/// Sent once when the worker is asked to shut down.
+///
+/// The worker is kept alive until [`persistence::PendingFlush`] is gone, so systems can use the
+/// remaining frames to write out the last state.
+#[derive(Event)]
+pub struct ShutdownEvent;
+
 impl From<EngineMessage> for OutputEvent {
     fn from(value: EngineMessage) -> Self {
         OutputEvent(value)
     }
 }
 
+/// Events and intent handling shared by every transport.
+///
+/// A transport plugin, such as the web worker or the in-memory channel, has to be added alongside
+/// this one to move messages in [`sets::Inputs`] and [`sets::Outputs`].
 pub struct InputOutputPlugin;
 
 impl Plugin for InputOutputPlugin {
@@ -42,12 +74,27 @@ impl Plugin for InputOutputPlugin {
         app.add_event::<InputEvent>()
             .add_event::<UpdatedEvent>()
             .add_event::<OutputEvent>()
+            .add_event::<ShutdownEvent>()
             .add_plugins(SchedulesPlugin)
-            .add_plugins(WorkerPlugin)
             .add_plugins(IntentResolverPlugin)
+            .add_plugins(SnapshotPlugin)
+            .add_systems(Last, batch_updates.before(sets::Outputs))
             .configure_sets(
                 First,
-                (worker::sets::Inputs, intent_resolver::sets::Main).chain(),
+                (
+                    sets::Inputs,
+                    intent_resolver::sets::Main,
+                    persistence::sets::Storage,
+                    persistence::sets::Load,
+                )
+                    .chain(),
             );
     }
 }
+
+pub fn batch_updates(
+    mut updates: ResMut<Events<UpdatedEvent>>,
+    mut outputs: EventWriter<OutputEvent>,
+) {
+    outputs.send(EngineMessage::Updated(updates.drain().map(|e| e.0).collect()).into());
+}
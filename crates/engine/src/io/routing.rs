@@ -0,0 +1,516 @@
+//! Which connected bridges the messages of the engine go to.
+//!
+//! Kept apart from the web worker that carries the messages, so that it also runs natively.
+
+use sorrow_core::communication::{EngineMessage, Intent, ProtocolInfo};
+
+/// Most messages held on to while no bridge is connected, after leaving out the ones that newer
+/// messages make redundant.
+const MAX_HELD_MESSAGES: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Role {
+    Player,
+    /// Receives everything, but only intents that leave the game as it is are accepted from it.
+    Spectator,
+}
+
+/// Messages that answer a single intent, and go only to the bridges that sent it.
+///
+/// Everything else concerns every bridge and is broadcast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Reply {
+    Handshake,
+    Saved,
+    Exported,
+    Replay,
+    Diagnostics,
+    Snapshot,
+}
+
+impl Reply {
+    fn requested_by(intent: &Intent) -> Option<Self> {
+        match intent {
+            Intent::Handshake(_) => Some(Self::Handshake),
+            Intent::Save => Some(Self::Saved),
+            Intent::Export => Some(Self::Exported),
+            Intent::ExportReplay => Some(Self::Replay),
+            Intent::ExportDiagnostics => Some(Self::Diagnostics),
+            Intent::Snapshot => Some(Self::Snapshot),
+            _ => None,
+        }
+    }
+
+    fn answered_by(message: &EngineMessage) -> Option<Self> {
+        match message {
+            EngineMessage::Handshake(_) => Some(Self::Handshake),
+            EngineMessage::Saved(_) => Some(Self::Saved),
+            EngineMessage::Exported(_) => Some(Self::Exported),
+            EngineMessage::Replay(_) => Some(Self::Replay),
+            EngineMessage::Diagnostics(_) => Some(Self::Diagnostics),
+            EngineMessage::Snapshot(_) => Some(Self::Snapshot),
+            _ => None,
+        }
+    }
+}
+
+/// Whether a spectator may send the intent, because it only reads the game.
+fn is_read_only(intent: &Intent) -> bool {
+    matches!(
+        intent,
+        Intent::Handshake(_)
+            | Intent::Save
+            | Intent::Export
+            | Intent::ExportReplay
+            | Intent::ExportDiagnostics
+            | Intent::ListSlots
+            | Intent::Snapshot
+    )
+}
+
+/// Whether a bridge was found to speak the protocol of the engine.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Handshake {
+    /// The bridge has not sent a handshake yet.
+    Pending,
+    Compatible,
+    /// The bridge speaks another protocol, so nothing but the handshake reply can be sent to it
+    /// and nothing it sends can be read.
+    Incompatible,
+}
+
+#[derive(Debug)]
+struct Connection<Id> {
+    id: Id,
+    role: Role,
+    handshake: Handshake,
+}
+
+/// Connected bridges, told apart by `Id`, and the messages on their way to them.
+///
+/// Bridges have to send a compatible [`Intent::Handshake`] before anything else. Until they do,
+/// only the reply to the handshake is sent to them and nothing else they send is accepted.
+#[derive(Debug)]
+pub struct Routing<Id> {
+    /// Connected bridges, in the order they connected.
+    connections: Vec<Connection<Id>>,
+    /// Bridges waiting on the reply to an intent they sent.
+    awaiting: Vec<(Reply, Id)>,
+    /// Messages to send, held on to while no compatible bridge is connected.
+    outputs: Vec<EngineMessage>,
+    /// Held messages that did not fit in [`MAX_HELD_MESSAGES`], since they were last sent.
+    dropped: usize,
+}
+
+impl<Id> Default for Routing<Id> {
+    fn default() -> Self {
+        Self {
+            connections: Vec::new(),
+            awaiting: Vec::new(),
+            outputs: Vec::new(),
+            dropped: 0,
+        }
+    }
+}
+
+impl<Id: Copy + Eq> Routing<Id> {
+    pub fn connect(&mut self, id: Id) {
+        self.connections.push(Connection {
+            id,
+            role: Role::Player,
+            handshake: Handshake::Pending,
+        });
+    }
+
+    pub fn disconnect(&mut self, id: Id) {
+        self.connections.retain(|connection| connection.id != id);
+        self.awaiting.retain(|(_, handler)| *handler != id);
+    }
+
+    pub fn is_connected(&self) -> bool {
+        !self.connections.is_empty()
+    }
+
+    /// Takes an intent from a bridge, and returns the intents to be handled by the engine.
+    pub fn accept(&mut self, intent: Intent, id: Id) -> Vec<Intent> {
+        let Some(connection) = self
+            .connections
+            .iter_mut()
+            .find(|connection| connection.id == id)
+        else {
+            tracing::warn!("Ignoring an intent from a bridge that is not connected");
+            return Vec::new();
+        };
+
+        if let Intent::Handshake(client) = &intent {
+            let was = connection.handshake;
+            connection.handshake = if ProtocolInfo::current().is_compatible_with(client) {
+                Handshake::Compatible
+            } else {
+                Handshake::Incompatible
+            };
+            self.awaiting.push((Reply::Handshake, id));
+
+            // Whatever was sent before the bridge could read it is caught up on with a snapshot.
+            if was != Handshake::Compatible && connection.handshake == Handshake::Compatible {
+                self.awaiting.push((Reply::Snapshot, id));
+                return vec![intent, Intent::Snapshot];
+            }
+            return vec![intent];
+        }
+        if connection.handshake != Handshake::Compatible {
+            // An intent encoded by another protocol may read as a different one.
+            tracing::warn!("Ignoring an intent from a bridge without a compatible handshake");
+            return Vec::new();
+        }
+
+        if let Intent::Spectate = intent {
+            connection.role = Role::Spectator;
+            return Vec::new();
+        }
+        if connection.role == Role::Spectator && !is_read_only(&intent) {
+            tracing::warn!("Ignoring {intent:?} from a spectator");
+            return Vec::new();
+        }
+
+        if let Some(reply) = Reply::requested_by(&intent) {
+            self.awaiting.push((reply, id));
+        }
+        vec![intent]
+    }
+
+    pub fn push(&mut self, messages: impl IntoIterator<Item = EngineMessage>) {
+        self.outputs.extend(messages);
+    }
+
+    /// Forgets the messages that were not sent yet.
+    pub fn clear(&mut self) {
+        self.outputs.clear();
+    }
+
+    /// Takes the messages to send, along with the bridge each of them goes to.
+    ///
+    /// Messages for every bridge are held on to while none of them has a compatible handshake.
+    pub fn deliver(&mut self) -> Vec<(Id, EngineMessage)> {
+        let broadcast: Vec<Id> = self
+            .connections
+            .iter()
+            .filter(|connection| connection.handshake == Handshake::Compatible)
+            .map(|connection| connection.id)
+            .collect();
+        if !broadcast.is_empty() {
+            let dropped = std::mem::take(&mut self.dropped);
+            if dropped > 0 {
+                tracing::warn!("Dropped {dropped} messages while no bridge was connected");
+            }
+        }
+
+        let mut deliveries = Vec::new();
+        let mut held = Vec::new();
+        for message in std::mem::take(&mut self.outputs) {
+            match Reply::answered_by(&message) {
+                Some(reply) => {
+                    for id in self.waiting_for(reply) {
+                        deliveries.push((id, message.clone()));
+                    }
+                }
+                None if broadcast.is_empty() => held.push(message),
+                None => {
+                    for id in &broadcast {
+                        deliveries.push((*id, message.clone()));
+                    }
+                }
+            }
+        }
+
+        if !held.is_empty() {
+            self.outputs = held;
+            self.hold();
+        }
+        deliveries
+    }
+
+    /// Keeps the messages until a bridge can read them, leaving out the ones that will be redundant.
+    pub fn hold(&mut self) {
+        // Bridges are sent a snapshot once their handshake is found compatible, which covers every
+        // update, and replies were meant for bridges that are gone.
+        self.outputs.retain(|message| {
+            !matches!(message, EngineMessage::Updated(_)) && Reply::answered_by(message).is_none()
+        });
+
+        // Only the latest list of slots is still accurate.
+        let latest_slots = self
+            .outputs
+            .iter()
+            .rposition(|message| matches!(message, EngineMessage::Slots(_)));
+        let mut index = 0;
+        self.outputs.retain(|message| {
+            let redundant =
+                matches!(message, EngineMessage::Slots(_)) && Some(index) != latest_slots;
+            index += 1;
+            !redundant
+        });
+
+        if self.outputs.len() > MAX_HELD_MESSAGES {
+            let excess = self.outputs.len() - MAX_HELD_MESSAGES;
+            self.outputs.drain(..excess);
+            self.dropped += excess;
+            tracing::debug!("Dropping the {excess} oldest messages held for a bridge to connect");
+        }
+    }
+
+    /// Bridges waiting on the reply, which are no longer waiting once this returns.
+    fn waiting_for(&mut self, reply: Reply) -> Vec<Id> {
+        // Requests of the same kind in a frame are answered together, so everyone waiting gets the
+        // same answer.
+        let mut waiting = Vec::new();
+        self.awaiting.retain(|(awaited, id)| {
+            let answered = *awaited == reply;
+            if answered {
+                waiting.push(*id);
+            }
+            !answered
+        });
+        if waiting.is_empty() {
+            tracing::debug!("Dropping a {reply:?} reply whose bridge disconnected");
+        }
+        waiting
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sorrow_core::{
+        communication::{HandshakeReply, Snapshot, WorkOrderKind},
+        persistence::SavePayload,
+        state::recipes::CraftingRecipeKind,
+    };
+
+    use super::*;
+
+    const GATHER_CATNIP: Intent =
+        Intent::QueueWorkOrder(WorkOrderKind::Craft(CraftingRecipeKind::GatherCatnip));
+
+    fn saved() -> EngineMessage {
+        EngineMessage::Saved(SavePayload(String::new()))
+    }
+
+    fn snapshot() -> EngineMessage {
+        EngineMessage::Snapshot(Box::<Snapshot>::default())
+    }
+
+    fn handshake_reply() -> EngineMessage {
+        EngineMessage::Handshake(HandshakeReply {
+            engine: ProtocolInfo::current(),
+            compatible: true,
+        })
+    }
+
+    fn handshake() -> Intent {
+        Intent::Handshake(ProtocolInfo::current())
+    }
+
+    fn incompatible_handshake() -> Intent {
+        let current = ProtocolInfo::current();
+        Intent::Handshake(ProtocolInfo {
+            version: current.version + 1,
+            ..current
+        })
+    }
+
+    /// Bridges that connected, shook hands and were sent their snapshot.
+    fn connected(ids: &[u32]) -> Routing<u32> {
+        let mut routing = Routing::default();
+        for id in ids {
+            routing.connect(*id);
+            routing.accept(handshake(), *id);
+        }
+        routing.push([handshake_reply(), snapshot()]);
+        routing.deliver();
+        routing
+    }
+
+    fn recipients(deliveries: &[(u32, EngineMessage)]) -> Vec<u32> {
+        deliveries.iter().map(|(id, _)| *id).collect()
+    }
+
+    #[test]
+    fn replies_answer_the_intents_that_request_them() {
+        let pairs = [
+            (Intent::Handshake(ProtocolInfo::current()), Reply::Handshake),
+            (Intent::Save, Reply::Saved),
+            (Intent::Export, Reply::Exported),
+            (Intent::ExportReplay, Reply::Replay),
+            (Intent::ExportDiagnostics, Reply::Diagnostics),
+            (Intent::Snapshot, Reply::Snapshot),
+        ];
+        for (intent, reply) in pairs {
+            assert_eq!(Reply::requested_by(&intent), Some(reply), "{intent:?}");
+        }
+        assert_eq!(Reply::requested_by(&GATHER_CATNIP), None);
+
+        assert_eq!(Reply::answered_by(&saved()), Some(Reply::Saved));
+        assert_eq!(Reply::answered_by(&snapshot()), Some(Reply::Snapshot));
+        assert_eq!(Reply::answered_by(&EngineMessage::Loaded), None);
+    }
+
+    #[test]
+    fn only_reading_intents_are_read_only() {
+        assert!(is_read_only(&Intent::Save));
+        assert!(is_read_only(&Intent::ListSlots));
+        assert!(is_read_only(&Intent::Snapshot));
+        assert!(!is_read_only(&GATHER_CATNIP));
+        assert!(!is_read_only(&Intent::Import(String::new())));
+        assert!(!is_read_only(&Intent::Spectate));
+    }
+
+    #[test]
+    fn spectators_only_send_read_only_intents() {
+        let mut routing = connected(&[1]);
+        assert_eq!(routing.accept(GATHER_CATNIP, 1).len(), 1);
+
+        assert!(routing.accept(Intent::Spectate, 1).is_empty());
+        assert!(routing.accept(GATHER_CATNIP, 1).is_empty());
+        assert!(matches!(
+            routing.accept(Intent::Save, 1).as_slice(),
+            [Intent::Save]
+        ));
+    }
+
+    #[test]
+    fn ignores_bridges_that_are_not_connected() {
+        let mut routing = connected(&[1]);
+        assert!(routing.accept(GATHER_CATNIP, 2).is_empty());
+
+        routing.disconnect(1);
+        assert!(routing.accept(GATHER_CATNIP, 1).is_empty());
+    }
+
+    #[test]
+    fn sends_replies_to_the_bridges_that_asked() {
+        let mut routing = connected(&[1, 2, 3]);
+        routing.accept(Intent::Save, 1);
+        routing.accept(Intent::Save, 3);
+        routing.push([saved(), EngineMessage::Loaded]);
+
+        let deliveries = routing.deliver();
+        assert!(matches!(deliveries[0].1, EngineMessage::Saved(_)));
+        assert_eq!(recipients(&deliveries[..2]), [1, 3]);
+        assert_eq!(recipients(&deliveries[2..]), [1, 2, 3]);
+    }
+
+    #[test]
+    fn ignores_intents_until_a_compatible_handshake() {
+        let mut routing = Routing::default();
+        routing.connect(1);
+        assert!(routing.accept(GATHER_CATNIP, 1).is_empty());
+        assert!(routing.accept(Intent::Snapshot, 1).is_empty());
+
+        assert!(matches!(
+            routing.accept(handshake(), 1).as_slice(),
+            [Intent::Handshake(_), Intent::Snapshot]
+        ));
+        assert_eq!(routing.accept(GATHER_CATNIP, 1).len(), 1);
+    }
+
+    #[test]
+    fn sends_the_snapshot_only_to_a_bridge_that_shakes_hands() {
+        let mut routing = connected(&[1]);
+        routing.connect(2);
+        routing.accept(handshake(), 2);
+        routing.push([snapshot()]);
+        assert_eq!(recipients(&routing.deliver()), [2]);
+    }
+
+    #[test]
+    fn sends_only_the_handshake_to_an_incompatible_bridge() {
+        let mut routing = connected(&[1]);
+        routing.connect(2);
+        assert!(matches!(
+            routing.accept(incompatible_handshake(), 2).as_slice(),
+            [Intent::Handshake(_)]
+        ));
+        assert!(routing.accept(GATHER_CATNIP, 2).is_empty());
+        assert!(routing.accept(Intent::Snapshot, 2).is_empty());
+
+        routing.push([
+            handshake_reply(),
+            EngineMessage::Updated(Vec::new()),
+            EngineMessage::Loaded,
+        ]);
+        let deliveries = routing.deliver();
+        assert!(matches!(
+            deliveries.as_slice(),
+            [
+                (2, EngineMessage::Handshake(_)),
+                (1, EngineMessage::Updated(_)),
+                (1, EngineMessage::Loaded)
+            ]
+        ));
+    }
+
+    #[test]
+    fn holds_broadcasts_until_a_bridge_shakes_hands() {
+        let mut routing = Routing::default();
+        routing.connect(1);
+        routing.push([EngineMessage::Updated(Vec::new()), EngineMessage::Loaded]);
+        assert!(routing.deliver().is_empty());
+
+        routing.accept(handshake(), 1);
+        routing.push([snapshot()]);
+        let deliveries = routing.deliver();
+        assert!(matches!(
+            deliveries.as_slice(),
+            [(1, EngineMessage::Loaded), (1, EngineMessage::Snapshot(_))]
+        ));
+    }
+
+    #[test]
+    fn drops_replies_to_bridges_that_disconnected() {
+        let mut routing = connected(&[1, 2]);
+        routing.accept(Intent::Export, 1);
+        routing.disconnect(1);
+        routing.push([EngineMessage::Exported(String::new())]);
+        assert!(routing.deliver().is_empty());
+    }
+
+    #[test]
+    fn holds_only_what_a_connecting_bridge_still_needs() {
+        let mut routing = connected(&[1]);
+        routing.accept(Intent::ExportDiagnostics, 1);
+        routing.disconnect(1);
+        routing.push([
+            EngineMessage::Slots(Vec::new()),
+            EngineMessage::Updated(Vec::new()),
+            EngineMessage::Loaded,
+            EngineMessage::Diagnostics(String::new()),
+            EngineMessage::Slots(Vec::new()),
+            snapshot(),
+        ]);
+        routing.hold();
+
+        routing.connect(2);
+        routing.accept(handshake(), 2);
+        let deliveries = routing.deliver();
+        assert!(matches!(
+            deliveries.as_slice(),
+            [(2, EngineMessage::Loaded), (2, EngineMessage::Slots(_))]
+        ));
+    }
+
+    #[test]
+    fn holds_a_bounded_number_of_messages() {
+        let mut routing = Routing::<u32>::default();
+        for _ in 0..MAX_HELD_MESSAGES + 10 {
+            routing.push([EngineMessage::Loaded]);
+            routing.hold();
+        }
+        assert_eq!(routing.outputs.len(), MAX_HELD_MESSAGES);
+        assert_eq!(routing.dropped, 10);
+
+        routing.connect(1);
+        routing.accept(handshake(), 1);
+        assert_eq!(routing.deliver().len(), MAX_HELD_MESSAGES);
+        assert_eq!(routing.dropped, 0);
+    }
+}
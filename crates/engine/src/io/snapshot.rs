@@ -0,0 +1,35 @@
+use bevy::{
+    app::{Last, Plugin},
+    prelude::*,
+};
+
+use sorrow_core::communication::{EngineMessage, Snapshot};
+
+use super::{batch_updates, OutputEvent};
+
+/// Present while a [`Snapshot`] is being filled in, to be sent out at the end of the frame.
+///
+/// Every domain that reports changes also fills in its part of the snapshot in
+/// [`crate::schedules::BufferChanges`], after the frame's changes are done.
+#[derive(Resource, Debug, Default)]
+pub struct PendingSnapshot(pub Snapshot);
+
+pub struct SnapshotPlugin;
+
+impl Plugin for SnapshotPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Last, send_snapshot.before(batch_updates));
+    }
+}
+
+fn send_snapshot(
+    mut cmd: Commands,
+    snapshot: Option<ResMut<PendingSnapshot>>,
+    mut outputs: EventWriter<OutputEvent>,
+) {
+    if let Some(mut snapshot) = snapshot {
+        let snapshot = Box::new(std::mem::take(&mut snapshot.0));
+        outputs.send(EngineMessage::Snapshot(snapshot).into());
+        cmd.remove_resource::<PendingSnapshot>();
+    }
+}
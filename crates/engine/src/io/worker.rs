@@ -1,6 +1,6 @@
 use bevy::{
     app::{First, Last, Plugin},
-    prelude::{EventWriter, Events, IntoSystemConfigs, NonSend, ResMut},
+    prelude::{EventWriter, Events, IntoSystemConfigs, NonSend, Res, ResMut},
 };
 use sorrow_core::{
     communication::{EngineMessage, Intent},
@@ -8,13 +8,32 @@ use sorrow_core::{
 };
 use sorrow_worker::{HandlerId, Registrable, WorkerDestroyHandle, WorkerScope};
 
-use super::{InputEvent, OutputEvent, UpdatedEvent};
+use crate::persistence::PendingFlush;
+
+use super::{routing::Routing, sets, InputEvent, OutputEvent, ShutdownEvent};
+
+/// Encoding of the messages between the engine and its bridges, which both sides have to agree on.
+#[cfg(not(feature = "json-messages"))]
+pub type Encoding = sorrow_worker::Bincode;
+/// Encoding of the messages between the engine and its bridges, which both sides have to agree on.
+#[cfg(feature = "json-messages")]
+pub type Encoding = sorrow_worker::Json;
 
 pub struct Dispatcher {
     inputs: Vec<Intent>,
-    outputs: Vec<EngineMessage>,
     scope: Option<WorkerScope<Worker>>,
-    handler_id: Option<HandlerId>,
+    routing: Routing<HandlerId>,
+    shutdown: Shutdown,
+}
+
+/// Progress of a requested shutdown. The worker is destroyed when the handle is dropped.
+enum Shutdown {
+    None,
+    Requested(WorkerDestroyHandle<Worker>),
+    Announced(
+        #[expect(dead_code, reason = "held to keep the worker alive")] WorkerDestroyHandle<Worker>,
+    ),
+    Done,
 }
 
 impl Dispatcher {
@@ -23,28 +42,60 @@ impl Dispatcher {
     }
 
     fn connected(&mut self, id: HandlerId) {
-        self.handler_id = Some(id);
+        // Nothing is sent until the bridge shakes hands, since it may speak another protocol.
+        self.routing.connect(id);
     }
 
-    fn disconnected(&mut self) {
-        self.handler_id = None;
+    fn disconnected(&mut self, id: HandlerId) {
+        self.routing.disconnect(id);
     }
 
-    fn received(&mut self, msg: Intent) {
-        self.inputs.push(msg)
+    fn received(&mut self, msg: Intent, id: HandlerId) {
+        let intents = self.routing.accept(msg, id);
+        self.inputs.extend(intents);
     }
 
-    fn destroyed(&mut self) {
-        self.scope = None;
+    fn destroyed(&mut self, handle: WorkerDestroyHandle<Worker>) {
+        self.shutdown = Shutdown::Requested(handle);
     }
 
-    fn send_responses(&mut self) {
-        if let (Some(scope), Some(handler_id)) = (self.scope.clone(), self.handler_id) {
-            for message in self.outputs.drain(..) {
-                scope.respond(handler_id, message);
+    fn announce_shutdown(&mut self) -> bool {
+        match std::mem::replace(&mut self.shutdown, Shutdown::None) {
+            Shutdown::Requested(handle) => {
+                self.shutdown = Shutdown::Announced(handle);
+                true
+            }
+            other => {
+                self.shutdown = other;
+                false
             }
+        }
+    }
+
+    fn release(&mut self) {
+        if matches!(self.shutdown, Shutdown::Announced(_)) {
+            // Dropping the handle destroys the worker.
+            self.shutdown = Shutdown::Done;
+            self.scope = None;
+        }
+    }
+
+    fn send_responses(&mut self) {
+        if matches!(self.shutdown, Shutdown::Done) {
+            self.routing.clear();
+        } else if self.routing.is_connected() {
+            self.flush();
         } else {
-            panic!("Could not send responses because there was no connection");
+            self.routing.hold();
+        }
+    }
+
+    fn flush(&mut self) {
+        let Some(scope) = self.scope.clone() else {
+            return;
+        };
+        for (id, message) in self.routing.deliver() {
+            scope.respond(id, message);
         }
     }
 }
@@ -81,17 +132,17 @@ impl sorrow_worker::Worker for Worker {
     }
 
     #[tracing::instrument(level = "trace", fields(id), skip_all)]
-    fn disconnected(&mut self, _: &WorkerScope<Self>, _: HandlerId) {
-        self.dispatcher().borrow_mut().disconnected();
+    fn disconnected(&mut self, _: &WorkerScope<Self>, id: HandlerId) {
+        self.dispatcher().borrow_mut().disconnected(id);
     }
 
     #[tracing::instrument(level = "trace", fields(msg), skip_all)]
-    fn received(&mut self, _: &WorkerScope<Self>, msg: Self::Input, _: HandlerId) {
-        self.dispatcher().borrow_mut().received(msg);
+    fn received(&mut self, _: &WorkerScope<Self>, msg: Self::Input, id: HandlerId) {
+        self.dispatcher().borrow_mut().received(msg, id);
     }
 
-    fn destroy(&mut self, _: &WorkerScope<Self>, _: WorkerDestroyHandle<Self>) {
-        self.dispatcher().borrow_mut().destroyed();
+    fn destroy(&mut self, _: &WorkerScope<Self>, handle: WorkerDestroyHandle<Self>) {
+        self.dispatcher().borrow_mut().destroyed(handle);
     }
 
     fn update(&mut self, _: &WorkerScope<Self>, _: Self::Message) {
@@ -99,47 +150,49 @@ impl sorrow_worker::Worker for Worker {
     }
 }
 
-pub mod sets {
-    use bevy::prelude::SystemSet;
-
-    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
-    pub struct Inputs;
-
-    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
-    pub struct Outputs;
-}
-
 pub struct WorkerPlugin;
 
 impl Plugin for WorkerPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         let dispatcher = Shared::new(Dispatcher {
             inputs: Vec::<Intent>::new(),
-            outputs: Vec::<EngineMessage>::new(),
-            handler_id: None,
             scope: None,
+            routing: Routing::default(),
+            shutdown: Shutdown::None,
         });
-        Worker::registrar().register_with(dispatcher.clone());
+        Worker::registrar()
+            .encoding::<Encoding>()
+            .register_with(dispatcher.clone());
 
         app.insert_non_send_resource(dispatcher)
             .add_systems(First, receive_inputs.in_set(sets::Inputs))
             .add_systems(
                 Last,
-                (batch_updates, send_outputs).chain().in_set(sets::Outputs),
+                (send_outputs, release_worker).chain().in_set(sets::Outputs),
             );
     }
 }
 
-fn receive_inputs(mut inputs: EventWriter<InputEvent>, dispatcher: NonSend<Shared<Dispatcher>>) {
-    inputs.send_batch(dispatcher.borrow_mut().inputs.drain(..).map(InputEvent));
-}
-
-fn batch_updates(mut updates: ResMut<Events<UpdatedEvent>>, mut outputs: EventWriter<OutputEvent>) {
-    outputs.send(EngineMessage::Updated(updates.drain().map(|e| e.0).collect()).into());
+fn receive_inputs(
+    mut inputs: EventWriter<InputEvent>,
+    mut shutdowns: EventWriter<ShutdownEvent>,
+    dispatcher: NonSend<Shared<Dispatcher>>,
+) {
+    let mut dispatcher = dispatcher.borrow_mut();
+    inputs.send_batch(dispatcher.inputs.drain(..).map(InputEvent));
+    if dispatcher.announce_shutdown() {
+        shutdowns.send(ShutdownEvent);
+    }
 }
 
 fn send_outputs(mut outputs: ResMut<Events<OutputEvent>>, dispatcher: NonSend<Shared<Dispatcher>>) {
     let mut dispatcher = dispatcher.borrow_mut();
-    dispatcher.outputs.extend(outputs.drain().map(|e| e.0));
+    dispatcher.routing.push(outputs.drain().map(|e| e.0));
     dispatcher.send_responses();
 }
+
+fn release_worker(dispatcher: NonSend<Shared<Dispatcher>>, flush: Option<Res<PendingFlush>>) {
+    if flush.is_none() {
+        dispatcher.borrow_mut().release();
+    }
+}
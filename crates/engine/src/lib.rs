@@ -1,29 +1,80 @@
+#[cfg(feature = "headless")]
+pub mod bot;
+mod diagnostics;
+#[cfg(feature = "wasm")]
 mod endpoint;
+#[cfg(feature = "headless")]
+mod headless;
 mod index;
 mod io;
+mod persistence;
+mod replay;
 mod runner;
 mod schedules;
 mod simulation;
+#[cfg(feature = "headless")]
+pub mod testing;
 mod ui;
 
-pub use endpoint::Endpoint;
+use bevy::app::{PluginGroup, PluginGroupBuilder};
+
+#[cfg(feature = "wasm")]
+pub use endpoint::{CodecError, Endpoint};
+#[cfg(feature = "headless")]
+pub use headless::HeadlessEngine;
+pub use persistence::{export, storage};
+#[cfg(feature = "headless")]
+pub use replay::playback::{play_back, PlaybackError, PlaybackOutcome};
+#[cfg(feature = "headless")]
+pub use runner::HeadlessRunnerPlugin;
+
+use diagnostics::DiagnosticsPlugin;
+use io::InputOutputPlugin;
+use persistence::{storage::SaveStorage, PersistencePlugin, DEFAULT_AUTOSAVE_INTERVAL};
+use replay::ReplayPlugin;
+use simulation::SimulationPlugin;
 use ui::UiPlugin;
 
+/// The simulation and everything around it, short of a runner and a transport.
+struct EnginePlugins {
+    storage: Box<dyn SaveStorage>,
+}
+
+impl PluginGroup for EnginePlugins {
+    fn build(self) -> PluginGroupBuilder {
+        PluginGroupBuilder::start::<Self>()
+            .add(SimulationPlugin)
+            .add(InputOutputPlugin)
+            .add(PersistencePlugin::new(
+                self.storage,
+                DEFAULT_AUTOSAVE_INTERVAL,
+            ))
+            .add(ReplayPlugin)
+            .add(DiagnosticsPlugin)
+            .add(UiPlugin)
+    }
+}
+
+#[cfg(feature = "wasm")]
 pub fn start() {
     use std::time::Duration;
 
     use bevy::app::App;
     use bevy::log::LogPlugin;
 
-    use io::InputOutputPlugin;
+    use io::WorkerPlugin;
+    use persistence::storage::BrowserStorage;
     use runner::TimeoutRunnerPlugin;
-    use simulation::SimulationPlugin;
 
     App::new()
         .add_plugins(TimeoutRunnerPlugin::new(Duration::from_millis(20)))
-        .add_plugins(LogPlugin::default())
-        .add_plugins(SimulationPlugin)
-        .add_plugins(InputOutputPlugin)
-        .add_plugins(UiPlugin)
+        .add_plugins(LogPlugin {
+            custom_layer: diagnostics::log_layer,
+            ..Default::default()
+        })
+        .add_plugins(EnginePlugins {
+            storage: Box::<BrowserStorage>::default(),
+        })
+        .add_plugins(WorkerPlugin)
         .run();
 }
@@ -0,0 +1,47 @@
+use bevy::{
+    app::{App, FixedUpdate, Plugin, Startup},
+    prelude::*,
+};
+
+use crate::simulation::{ticker, ticker::Ticker, time};
+
+use super::{SaveRequests, SaveTarget};
+
+/// Number of game ticks between autosaves, i.e. 30 seconds at the default tick rate.
+pub const DEFAULT_AUTOSAVE_INTERVAL: u32 = 150;
+
+/// Number of game ticks between autosaves in this engine.
+#[derive(Resource, Debug, Clone, Copy)]
+pub struct AutosaveInterval(pub u32);
+
+#[derive(Component)]
+struct AutosaveTicker;
+
+pub struct AutosavePlugin {
+    pub interval: u32,
+}
+
+impl Plugin for AutosavePlugin {
+    fn build(&self, app: &mut App) {
+        let interval = self.interval;
+        app.insert_resource(AutosaveInterval(interval))
+            .add_systems(Startup, move |mut cmd: Commands| {
+                cmd.spawn((AutosaveTicker, Ticker::from_scale(interval)));
+            })
+            .add_systems(
+                FixedUpdate,
+                queue_autosave
+                    .after(ticker::sets::Main)
+                    .run_if(time::is_running),
+            );
+    }
+}
+
+fn queue_autosave(
+    ticker: Single<&Ticker, With<AutosaveTicker>>,
+    mut requests: ResMut<SaveRequests>,
+) {
+    if ticker.just_ticked() {
+        requests.request(SaveTarget::Storage);
+    }
+}
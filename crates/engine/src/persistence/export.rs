@@ -0,0 +1,58 @@
+//! Portable text encoding of saves, for moving games between machines.
+//!
+//! The text is the standard base64 encoding of a big-endian CRC32 checksum of the payload,
+//! followed by the deflate-compressed [`SavePayload`]. Other documents, such as diagnostic
+//! bundles, are packed the same way with [`pack`] and [`unpack`].
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use miniz_oxide::{deflate::compress_to_vec, inflate::decompress_to_vec_with_limit};
+
+use sorrow_core::persistence::{LoadError, SavePayload};
+
+const CHECKSUM_LEN: usize = 4;
+const COMPRESSION_LEVEL: u8 = 9;
+const MAX_PAYLOAD_LEN: usize = 16 * 1024 * 1024;
+
+pub fn encode(payload: &SavePayload) -> String {
+    pack(&payload.0)
+}
+
+/// Decodes exported text back into a payload.
+///
+/// Whitespace is ignored, so that text which was wrapped while being copied around still decodes.
+pub fn decode(text: &str) -> Result<SavePayload, LoadError> {
+    unpack(text).map(SavePayload)
+}
+
+pub fn pack(document: &str) -> String {
+    let checksum = crc32fast::hash(document.as_bytes());
+    let compressed = compress_to_vec(document.as_bytes(), COMPRESSION_LEVEL);
+
+    let mut bytes = Vec::with_capacity(CHECKSUM_LEN + compressed.len());
+    bytes.extend_from_slice(&checksum.to_be_bytes());
+    bytes.extend_from_slice(&compressed);
+    STANDARD.encode(bytes)
+}
+
+pub fn unpack(text: &str) -> Result<String, LoadError> {
+    let text = text
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .collect::<String>();
+    let bytes = STANDARD
+        .decode(text)
+        .map_err(|e| LoadError::Corrupted(e.to_string()))?;
+    if bytes.len() < CHECKSUM_LEN {
+        return Err(LoadError::Corrupted("text is too short".to_string()));
+    }
+
+    let (checksum, compressed) = bytes.split_at(CHECKSUM_LEN);
+    let checksum = u32::from_be_bytes(checksum.try_into().expect("checksum has 4 bytes"));
+    let decompressed = decompress_to_vec_with_limit(compressed, MAX_PAYLOAD_LEN)
+        .map_err(|e| LoadError::Corrupted(e.to_string()))?;
+    if crc32fast::hash(&decompressed) != checksum {
+        return Err(LoadError::Corrupted("checksum does not match".to_string()));
+    }
+
+    String::from_utf8(decompressed).map_err(|e| LoadError::Corrupted(e.to_string()))
+}
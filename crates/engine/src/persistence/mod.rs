@@ -0,0 +1,289 @@
+mod autosave;
+pub mod export;
+pub mod rewind;
+pub mod slots;
+pub mod storage;
+
+use std::{cell::Cell, collections::HashMap};
+
+use bevy::{
+    app::{App, First, Plugin, Startup},
+    ecs::system::SystemParam,
+    prelude::*,
+};
+
+use send_wrapper::SendWrapper;
+use sorrow_core::{
+    communication::EngineMessage,
+    persistence::{SavePayload, SaveState},
+};
+
+use crate::io::{OutputEvent, ShutdownEvent};
+
+use autosave::AutosavePlugin;
+use rewind::{RewindHistory, RewindPlugin};
+use slots::{SlotRequest, Slots};
+use storage::{SaveStorage, StorageEvent};
+
+pub use autosave::{AutosaveInterval, DEFAULT_AUTOSAVE_INTERVAL};
+
+pub mod sets {
+    use bevy::prelude::SystemSet;
+
+    /// Systems that apply the results of completed storage requests.
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Storage;
+
+    /// Domain systems that rebuild their entities from [`super::PendingLoad`].
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Load;
+
+    /// Domain systems that write their state into [`super::PendingSave`].
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Save;
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Complete;
+}
+
+/// State to rebuild the world from during this frame.
+#[derive(Resource, Debug)]
+pub struct PendingLoad(pub SaveState);
+
+/// State being collected from the world when saves have been requested.
+#[derive(Resource, Debug, Default)]
+pub struct PendingSave(pub SaveState);
+
+/// Where a collected state is delivered once the save completes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveTarget {
+    /// Answered with [`EngineMessage::Saved`].
+    Client,
+    /// Answered with [`EngineMessage::Exported`].
+    Export,
+    /// Written to the active save slot, if there is one.
+    Storage,
+    /// Answered with [`EngineMessage::Replay`] by the replay recording.
+    Replay,
+    /// Kept as the state that a full replay recording starts over from.
+    Recording,
+    /// Kept as the snapshot to rewind to for the current in-game day.
+    Rewind,
+    /// Answered with [`EngineMessage::Diagnostics`] by the diagnostics collection.
+    Diagnostics,
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct SaveRequests(Vec<SaveTarget>);
+
+impl SaveRequests {
+    pub fn request(&mut self, target: SaveTarget) {
+        if !self.0.contains(&target) {
+            self.0.push(target);
+        }
+    }
+
+    pub fn contains(&self, target: SaveTarget) -> bool {
+        self.0.contains(&target)
+    }
+}
+
+/// Present while a save written on shutdown has not reached the storage yet.
+#[derive(Resource, Debug, Default)]
+pub struct PendingFlush {
+    /// Key the save was written to, once it has been.
+    key: Option<String>,
+}
+
+/// The save storage, along with the writes that it has not completed yet.
+struct Storage {
+    backend: Box<dyn SaveStorage>,
+    /// Number of writes in progress, by key.
+    writing: HashMap<String, usize>,
+}
+
+impl Storage {
+    fn new(backend: Box<dyn SaveStorage>) -> Self {
+        Self {
+            backend,
+            writing: HashMap::new(),
+        }
+    }
+
+    fn read(&mut self, key: &str) {
+        self.backend.read(key);
+    }
+
+    fn write(&mut self, key: &str, data: String) {
+        self.backend.write(key, data);
+        *self.writing.entry(key.to_string()).or_default() += 1;
+    }
+
+    fn delete(&mut self, key: &str) {
+        self.backend.delete(key);
+    }
+
+    fn poll(&mut self) -> Vec<StorageEvent> {
+        let events = self.backend.poll();
+        for event in &events {
+            if let StorageEvent::Written { key, .. } = event {
+                if let Some(count) = self.writing.get_mut(key) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.writing.remove(key);
+                    }
+                }
+            }
+        }
+        events
+    }
+
+    /// Whether writes to the key have not completed yet.
+    fn is_writing(&self, key: &str) -> bool {
+        self.writing.contains_key(key)
+    }
+}
+
+fn has_save_requests(requests: Res<SaveRequests>) -> bool {
+    !requests.0.is_empty()
+}
+
+pub struct PersistencePlugin {
+    /// Storage that save slots are kept in, taken when the plugin is built.
+    ///
+    /// Plugins have to be `Send`, while storages stay on the thread that builds the app.
+    storage: SendWrapper<Cell<Option<Box<dyn SaveStorage>>>>,
+    /// Number of game ticks between autosaves.
+    autosave_interval: u32,
+}
+
+impl PersistencePlugin {
+    pub fn new(storage: Box<dyn SaveStorage>, autosave_interval: u32) -> Self {
+        Self {
+            storage: SendWrapper::new(Cell::new(Some(storage))),
+            autosave_interval,
+        }
+    }
+}
+
+impl Plugin for PersistencePlugin {
+    fn build(&self, app: &mut App) {
+        let storage =
+            Cell::take(&self.storage).expect("the persistence plugin should only be built once");
+        app.insert_non_send_resource(Storage::new(storage))
+            .init_resource::<PendingSave>()
+            .init_resource::<SaveRequests>()
+            .init_resource::<Slots>()
+            .add_event::<SlotRequest>()
+            .add_plugins(AutosavePlugin {
+                interval: self.autosave_interval,
+            })
+            .add_plugins(RewindPlugin)
+            .add_systems(Startup, slots::read_index)
+            .add_systems(
+                First,
+                (
+                    slots::poll_storage,
+                    slots::handle_slot_requests,
+                    flush_on_shutdown,
+                    finish_flush,
+                )
+                    .chain()
+                    .in_set(sets::Storage),
+            )
+            .configure_sets(
+                First,
+                (
+                    sets::Storage,
+                    sets::Load.run_if(resource_exists::<PendingLoad>),
+                    sets::Save.run_if(has_save_requests),
+                    sets::Complete,
+                )
+                    .chain(),
+            )
+            .add_systems(First, (finish_load, finish_save).in_set(sets::Complete));
+    }
+}
+
+fn flush_on_shutdown(
+    mut cmd: Commands,
+    mut shutdowns: EventReader<ShutdownEvent>,
+    mut requests: ResMut<SaveRequests>,
+) {
+    if shutdowns.read().count() > 0 {
+        requests.request(SaveTarget::Storage);
+        cmd.init_resource::<PendingFlush>();
+    }
+}
+
+fn finish_flush(mut cmd: Commands, storage: NonSend<Storage>, flush: Option<Res<PendingFlush>>) {
+    let Some(key) = flush.as_ref().and_then(|flush| flush.key.as_deref()) else {
+        return;
+    };
+    // Earlier writes to the same slot may still be in progress, and the flush is the last of them.
+    if !storage.is_writing(key) {
+        cmd.remove_resource::<PendingFlush>();
+    }
+}
+
+fn finish_load(
+    mut cmd: Commands,
+    load: Option<Res<PendingLoad>>,
+    mut outputs: EventWriter<OutputEvent>,
+) {
+    if load.is_some() {
+        cmd.remove_resource::<PendingLoad>();
+        outputs.send(EngineMessage::Loaded.into());
+    }
+}
+
+/// Where saves are kept within the engine.
+#[derive(SystemParam)]
+struct Keepers<'w> {
+    storage: NonSendMut<'w, Storage>,
+    slots: ResMut<'w, Slots>,
+    history: ResMut<'w, RewindHistory>,
+    flush: Option<ResMut<'w, PendingFlush>>,
+}
+
+fn finish_save(
+    mut cmd: Commands,
+    mut requests: ResMut<SaveRequests>,
+    mut keepers: Keepers,
+    save: Res<PendingSave>,
+    mut outputs: EventWriter<OutputEvent>,
+) {
+    if requests.0.is_empty() {
+        return;
+    }
+
+    let payload = SavePayload::encode(&save.0);
+    for target in requests.0.drain(..) {
+        match target {
+            SaveTarget::Client => {
+                outputs.send(EngineMessage::Saved(payload.clone()).into());
+            }
+            SaveTarget::Export => {
+                outputs.send(EngineMessage::Exported(export::encode(&payload)).into());
+            }
+            SaveTarget::Storage => {
+                match keepers
+                    .slots
+                    .write_active(&mut keepers.storage, &save.0, &payload)
+                {
+                    Some(key) => {
+                        if let Some(flush) = keepers.flush.as_mut() {
+                            flush.key = Some(key);
+                        }
+                    }
+                    None => {
+                        // Nothing to flush, so shutdown does not need to wait.
+                        cmd.remove_resource::<PendingFlush>();
+                    }
+                }
+            }
+            SaveTarget::Replay | SaveTarget::Recording | SaveTarget::Diagnostics => {}
+            SaveTarget::Rewind => keepers.history.push(&save.0),
+        }
+    }
+}
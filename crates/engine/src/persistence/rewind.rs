@@ -0,0 +1,127 @@
+use std::collections::VecDeque;
+
+use bevy::{
+    app::{App, First, FixedUpdate, Plugin, Startup},
+    prelude::*,
+};
+
+use sorrow_core::persistence::SaveState;
+
+use crate::simulation::{
+    calendar::{self, DayTicker},
+    ticker::Ticker,
+    time::{self, PlayTime},
+};
+
+use super::{sets, PendingLoad, SaveRequests, SaveTarget};
+
+/// Number of in-game days that can be rewound.
+pub const REWIND_DAYS: usize = 100;
+
+#[derive(Event, Debug, Clone, Copy)]
+pub struct RewindRequest(pub u32);
+
+/// Snapshots taken at the start of each in-game day, oldest first.
+#[derive(Resource, Debug, Default)]
+pub struct RewindHistory {
+    snapshots: VecDeque<SaveState>,
+    /// Set while the world is being restored from one of the snapshots.
+    rewinding: bool,
+}
+
+impl RewindHistory {
+    pub(super) fn push(&mut self, state: &SaveState) {
+        if self.snapshots.len() == REWIND_DAYS {
+            self.snapshots.pop_front();
+        }
+        self.snapshots.push_back(SaveState {
+            // Rewinding is not time away, so there is nothing to catch up on.
+            saved_at: None,
+            ..state.clone()
+        });
+    }
+}
+
+pub struct RewindPlugin;
+
+impl Plugin for RewindPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<RewindHistory>()
+            .add_event::<RewindRequest>()
+            .add_systems(Startup, queue_first_snapshot)
+            .add_systems(First, rewind.in_set(sets::Storage))
+            .add_systems(First, reset_history.in_set(sets::Load))
+            .add_systems(
+                FixedUpdate,
+                queue_snapshot
+                    .after(calendar::sets::Main)
+                    .run_if(time::is_running),
+            );
+    }
+}
+
+fn queue_first_snapshot(mut requests: ResMut<SaveRequests>) {
+    requests.request(SaveTarget::Rewind);
+}
+
+fn queue_snapshot(
+    day_ticker: Single<&Ticker, With<DayTicker>>,
+    mut requests: ResMut<SaveRequests>,
+) {
+    if day_ticker.just_ticked() {
+        requests.request(SaveTarget::Rewind);
+    }
+}
+
+fn rewind(
+    mut cmd: Commands,
+    mut requests: EventReader<RewindRequest>,
+    mut history: ResMut<RewindHistory>,
+    mut save_requests: ResMut<SaveRequests>,
+    play_time: Res<PlayTime>,
+) {
+    let Some(RewindRequest(days)) = requests.read().last().copied() else {
+        return;
+    };
+    if days == 0 {
+        return;
+    }
+
+    let available = history.snapshots.len();
+    if available == 0 {
+        tracing::warn!("Ignoring a rewind of {days} days with no snapshots taken yet");
+        return;
+    }
+    let days = days as usize;
+    if days > available {
+        tracing::warn!("Rewinding {available} days instead of {days}, the oldest snapshot kept");
+    }
+    let days = days.min(available);
+
+    // The snapshot being restored stays, so that the same day can be rewound to again.
+    history.snapshots.truncate(available - days + 1);
+    let Some(snapshot) = history.snapshots.back() else {
+        return;
+    };
+    cmd.insert_resource(PendingLoad(SaveState {
+        // Play time is spent whether or not the game is rewound afterwards.
+        play_time_seconds: play_time.seconds,
+        ..snapshot.clone()
+    }));
+    history.rewinding = true;
+
+    // A snapshot collected now would duplicate the one being restored.
+    save_requests
+        .0
+        .retain(|target| *target != SaveTarget::Rewind);
+}
+
+fn reset_history(load: Res<PendingLoad>, mut history: ResMut<RewindHistory>) {
+    if std::mem::take(&mut history.rewinding) {
+        return;
+    }
+
+    // Snapshots of another game cannot be rewound to, but the loaded state is a fresh start.
+    history.snapshots.clear();
+    history.push(&load.0);
+}
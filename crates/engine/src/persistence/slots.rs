@@ -0,0 +1,317 @@
+use bevy::prelude::*;
+
+use sorrow_core::{
+    communication::EngineMessage,
+    persistence::{LoadError, SavePayload, SaveState, SlotId, SlotMetadata},
+    utils::unix_millis,
+};
+
+use crate::io::OutputEvent;
+
+use super::{storage::StorageEvent, PendingLoad, SaveRequests, SaveTarget, Storage};
+
+/// Key of the list of [`SlotMetadata`].
+const INDEX_KEY: &str = "slots";
+
+fn slot_key(id: SlotId) -> String {
+    format!("slot/{}", id.0)
+}
+
+#[derive(Event, Debug, Clone)]
+pub enum SlotRequest {
+    List,
+    Create(String),
+    Open(SlotId),
+    Rename(SlotId, String),
+    Duplicate(SlotId, String),
+    Delete(SlotId),
+}
+
+/// Save slots and the one that autosaves are currently written to.
+#[derive(Resource, Debug, Default)]
+pub struct Slots {
+    index: Vec<SlotMetadata>,
+    /// Whether the index has been read from the storage. Until then nothing is written, so that
+    /// the stored games cannot be overwritten.
+    loaded: bool,
+    active: Option<SlotId>,
+    /// Slot whose save is being read in order to open it.
+    opening: Option<SlotId>,
+    /// Requests that arrived before the index was read, run once it has been.
+    queued: Vec<SlotRequest>,
+    /// Requests to run once the active slot has been saved.
+    deferred: Vec<SlotRequest>,
+    /// Duplicates whose source has not been read yet.
+    duplicating: Vec<Duplicate>,
+}
+
+#[derive(Debug)]
+struct Duplicate {
+    source: SlotId,
+    target: SlotId,
+    /// Whether the source is being read. It is only read once no writes to it are in progress,
+    /// so that the copy is of its latest save.
+    reading: bool,
+}
+
+impl Slots {
+    pub fn active(&self) -> Option<&SlotMetadata> {
+        self.active.and_then(|id| self.get(id))
+    }
+
+    fn get(&self, id: SlotId) -> Option<&SlotMetadata> {
+        self.index.iter().find(|slot| slot.id == id)
+    }
+
+    fn get_mut(&mut self, id: SlotId) -> Option<&mut SlotMetadata> {
+        self.index.iter_mut().find(|slot| slot.id == id)
+    }
+
+    fn add(&mut self, name: String, state: &SaveState) -> SlotId {
+        let id = SlotId(
+            self.index
+                .iter()
+                .map(|slot| slot.id.0 + 1)
+                .max()
+                .unwrap_or(0),
+        );
+        self.index.push(SlotMetadata {
+            id,
+            name,
+            last_played: unix_millis(),
+            year: state.calendar.year,
+            play_time_seconds: state.play_time_seconds,
+        });
+        id
+    }
+
+    fn write_index(&self, storage: &mut Storage) {
+        if !self.loaded {
+            tracing::warn!("Not writing save slots before they have been read");
+            return;
+        }
+        match serde_json::to_string(&self.index) {
+            Ok(json) => storage.write(INDEX_KEY, json),
+            Err(error) => tracing::error!("Could not serialize save slots: {error}"),
+        }
+    }
+
+    /// Writes `state` to the active slot and refreshes its metadata.
+    ///
+    /// Returns the key written to, or `None` if there is no active slot to write to.
+    pub(super) fn write_active(
+        &mut self,
+        storage: &mut Storage,
+        state: &SaveState,
+        payload: &SavePayload,
+    ) -> Option<String> {
+        if !self.loaded {
+            return None;
+        }
+        let slot = self.active.and_then(|id| self.get_mut(id))?;
+        slot.last_played = state.saved_at.unwrap_or_else(unix_millis);
+        slot.year = state.calendar.year;
+        slot.play_time_seconds = state.play_time_seconds;
+        let key = slot_key(slot.id);
+        storage.write(&key, payload.0.clone());
+        self.write_index(storage);
+        Some(key)
+    }
+}
+
+pub(super) fn read_index(mut storage: NonSendMut<Storage>) {
+    storage.read(INDEX_KEY);
+}
+
+pub(super) fn poll_storage(
+    mut cmd: Commands,
+    mut storage: NonSendMut<Storage>,
+    mut slots: ResMut<Slots>,
+    mut outputs: EventWriter<OutputEvent>,
+) {
+    for event in storage.poll() {
+        match event {
+            StorageEvent::Read { key, result } if key == INDEX_KEY => match result {
+                // When the index cannot be read, slots stay unloaded so that the stored games are
+                // left as they are.
+                Ok(json) => {
+                    let index = json.map(|json| serde_json::from_str(&json)).transpose();
+                    match index {
+                        Ok(index) => {
+                            slots.index = index.unwrap_or_default();
+                            slots.loaded = true;
+                        }
+                        Err(error) => tracing::error!("Could not read save slots: {error}"),
+                    }
+                    outputs.send(EngineMessage::Slots(slots.index.clone()).into());
+                }
+                Err(error) => tracing::error!("Could not read save slots: {error}"),
+            },
+            StorageEvent::Read { key, result } => {
+                let duplicates = std::mem::take(&mut slots.duplicating);
+                for duplicate in duplicates {
+                    if !duplicate.reading || slot_key(duplicate.source) != key {
+                        slots.duplicating.push(duplicate);
+                    } else if let Ok(Some(data)) = &result {
+                        storage.write(&slot_key(duplicate.target), data.clone());
+                    }
+                }
+
+                let Some(id) = slots.opening.filter(|id| slot_key(*id) == key) else {
+                    continue;
+                };
+                slots.opening = None;
+
+                let decoded = match result {
+                    Ok(Some(data)) => SavePayload(data).decode(),
+                    Ok(None) => Err(LoadError::Storage("the slot is empty".to_string())),
+                    Err(error) => Err(LoadError::Storage(error.to_string())),
+                };
+                match decoded {
+                    Ok(state) => {
+                        cmd.insert_resource(PendingLoad(state));
+                        slots.active = Some(id);
+                        if let Some(slot) = slots.get(id) {
+                            outputs.send(EngineMessage::SlotOpened(slot.clone()).into());
+                        }
+                    }
+                    Err(error) => {
+                        tracing::warn!("Could not open save slot: {error}");
+                        outputs.send(EngineMessage::LoadFailed(error).into());
+                    }
+                }
+            }
+            StorageEvent::Written { key, result } => {
+                if let Err(error) = result {
+                    tracing::warn!("Could not write {key}: {error}");
+                }
+            }
+            StorageEvent::Deleted { key, result } => {
+                if let Err(error) = result {
+                    tracing::warn!("Could not delete {key}: {error}");
+                }
+            }
+        }
+    }
+}
+
+pub(super) fn handle_slot_requests(
+    mut cmd: Commands,
+    mut requests: EventReader<SlotRequest>,
+    mut storage: NonSendMut<Storage>,
+    mut slots: ResMut<Slots>,
+    mut save_requests: ResMut<SaveRequests>,
+    mut outputs: EventWriter<OutputEvent>,
+) {
+    let storage = storage.as_mut();
+
+    // New slots are numbered after the stored ones, so nothing can run until those are known.
+    if !slots.loaded {
+        slots.queued.extend(requests.read().cloned());
+        return;
+    }
+
+    // These were held back for a frame so that the active slot was saved before they run.
+    for request in std::mem::take(&mut slots.deferred) {
+        execute(
+            &mut cmd,
+            &request,
+            storage,
+            &mut slots,
+            &mut save_requests,
+            &mut outputs,
+        );
+    }
+
+    let queued = std::mem::take(&mut slots.queued);
+    for request in queued.iter().chain(requests.read()) {
+        match request {
+            SlotRequest::Create(_) | SlotRequest::Open(_) | SlotRequest::Duplicate(..) => {
+                save_requests.request(SaveTarget::Storage);
+                slots.deferred.push(request.clone());
+            }
+            _ => execute(
+                &mut cmd,
+                request,
+                storage,
+                &mut slots,
+                &mut save_requests,
+                &mut outputs,
+            ),
+        }
+    }
+
+    for duplicate in &mut slots.duplicating {
+        let key = slot_key(duplicate.source);
+        if !duplicate.reading && !storage.is_writing(&key) {
+            duplicate.reading = true;
+            storage.read(&key);
+        }
+    }
+}
+
+fn execute(
+    cmd: &mut Commands,
+    request: &SlotRequest,
+    storage: &mut Storage,
+    slots: &mut Slots,
+    save_requests: &mut SaveRequests,
+    outputs: &mut EventWriter<OutputEvent>,
+) {
+    match request {
+        SlotRequest::List => {}
+        SlotRequest::Create(name) => {
+            let state = SaveState::default();
+            let id = slots.add(name.clone(), &state);
+            slots.active = Some(id);
+            cmd.insert_resource(PendingLoad(state));
+            save_requests.request(SaveTarget::Storage);
+            if let Some(slot) = slots.get(id) {
+                outputs.send(EngineMessage::SlotOpened(slot.clone()).into());
+            }
+        }
+        SlotRequest::Open(id) => {
+            if slots.get(*id).is_none() {
+                tracing::warn!("Could not find save slot {id:?}");
+                return;
+            }
+            slots.opening = Some(*id);
+            storage.read(&slot_key(*id));
+        }
+        SlotRequest::Rename(id, name) => {
+            if let Some(slot) = slots.get_mut(*id) {
+                slot.name = name.clone();
+                slots.write_index(storage);
+            }
+        }
+        SlotRequest::Duplicate(id, name) => {
+            let Some(source) = slots.get(*id).cloned() else {
+                return;
+            };
+            let target = slots.add(name.clone(), &SaveState::default());
+            if let Some(slot) = slots.get_mut(target) {
+                slot.year = source.year;
+                slot.play_time_seconds = source.play_time_seconds;
+                slot.last_played = source.last_played;
+            }
+            // The source is read by `handle_slot_requests` once its writes are done.
+            slots.duplicating.push(Duplicate {
+                source: *id,
+                target,
+                reading: false,
+            });
+            slots.write_index(storage);
+        }
+        SlotRequest::Delete(id) => {
+            // Autosaves would have nowhere to go.
+            if slots.active == Some(*id) {
+                tracing::warn!("Not deleting save slot {id:?} while it is open");
+            } else {
+                slots.index.retain(|slot| slot.id != *id);
+                slots.write_index(storage);
+                storage.delete(&slot_key(*id));
+            }
+        }
+    }
+    outputs.send(EngineMessage::Slots(slots.index.clone()).into());
+}
@@ -0,0 +1,136 @@
+use sorrow_core::utils::Shared;
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_futures::{spawn_local, JsFuture};
+use web_sys::{
+    js_sys::{self, Promise},
+    IdbDatabase, IdbOpenDbRequest, IdbRequest, IdbTransactionMode, WorkerGlobalScope,
+};
+
+use super::{SaveStorage, StorageError, StorageEvent};
+
+const DATABASE_NAME: &str = "sorrow";
+const DATABASE_VERSION: u32 = 1;
+const STORE_NAME: &str = "saves";
+
+/// Storage backed by the IndexedDB of the worker's origin.
+///
+/// Workers have no access to `localStorage`, so IndexedDB is the only persistent storage
+/// available to the engine.
+#[derive(Default)]
+pub struct BrowserStorage {
+    events: Shared<Vec<StorageEvent>>,
+}
+
+impl SaveStorage for BrowserStorage {
+    fn read(&mut self, key: &str) {
+        let key = key.to_string();
+        let events = self.events.clone();
+        spawn_local(async move {
+            let result = read(&key).await;
+            events.borrow_mut().push(StorageEvent::Read { key, result });
+        });
+    }
+
+    fn write(&mut self, key: &str, data: String) {
+        let key = key.to_string();
+        let events = self.events.clone();
+        spawn_local(async move {
+            let result = write(&key, &data).await;
+            events
+                .borrow_mut()
+                .push(StorageEvent::Written { key, result });
+        });
+    }
+
+    fn delete(&mut self, key: &str) {
+        let key = key.to_string();
+        let events = self.events.clone();
+        spawn_local(async move {
+            let result = delete(&key).await;
+            events
+                .borrow_mut()
+                .push(StorageEvent::Deleted { key, result });
+        });
+    }
+
+    fn poll(&mut self) -> Vec<StorageEvent> {
+        std::mem::take(&mut *self.events.borrow_mut())
+    }
+}
+
+async fn read(key: &str) -> Result<Option<String>, StorageError> {
+    let database = open().await?;
+    let request = database
+        .transaction_with_str_and_mode(STORE_NAME, IdbTransactionMode::Readonly)
+        .and_then(|transaction| transaction.object_store(STORE_NAME))
+        .and_then(|store| store.get(&JsValue::from_str(key)))
+        .map_err(js_error)?;
+    let value = complete(&request).await?;
+    Ok(value.as_string())
+}
+
+async fn write(key: &str, data: &str) -> Result<(), StorageError> {
+    let database = open().await?;
+    let request = database
+        .transaction_with_str_and_mode(STORE_NAME, IdbTransactionMode::Readwrite)
+        .and_then(|transaction| transaction.object_store(STORE_NAME))
+        .and_then(|store| store.put_with_key(&JsValue::from_str(data), &JsValue::from_str(key)))
+        .map_err(js_error)?;
+    complete(&request).await?;
+    Ok(())
+}
+
+async fn delete(key: &str) -> Result<(), StorageError> {
+    let database = open().await?;
+    let request = database
+        .transaction_with_str_and_mode(STORE_NAME, IdbTransactionMode::Readwrite)
+        .and_then(|transaction| transaction.object_store(STORE_NAME))
+        .and_then(|store| store.delete(&JsValue::from_str(key)))
+        .map_err(js_error)?;
+    complete(&request).await?;
+    Ok(())
+}
+
+async fn open() -> Result<IdbDatabase, StorageError> {
+    let factory = js_sys::global()
+        .dyn_into::<WorkerGlobalScope>()
+        .map_err(js_error)?
+        .indexed_db()
+        .map_err(js_error)?
+        .ok_or_else(|| StorageError("IndexedDB is not available".to_string()))?;
+    let request: IdbOpenDbRequest = factory
+        .open_with_u32(DATABASE_NAME, DATABASE_VERSION)
+        .map_err(js_error)?;
+
+    let upgrade = {
+        let request = request.clone();
+        wasm_bindgen::closure::Closure::<dyn FnMut()>::new(move || {
+            if let Ok(database) = request.result().and_then(|r| r.dyn_into::<IdbDatabase>()) {
+                if !database.object_store_names().contains(STORE_NAME) {
+                    if let Err(error) = database.create_object_store(STORE_NAME) {
+                        tracing::error!("Could not create save store: {error:?}");
+                    }
+                }
+            }
+        })
+    };
+    request.set_onupgradeneeded(Some(upgrade.as_ref().unchecked_ref()));
+
+    let database = complete(&request).await?;
+    request.set_onupgradeneeded(None);
+    database.dyn_into::<IdbDatabase>().map_err(js_error)
+}
+
+/// Waits for `request` to complete and returns its result.
+async fn complete(request: &IdbRequest) -> Result<JsValue, StorageError> {
+    let promise = Promise::new(&mut |resolve, reject| {
+        request.set_onsuccess(Some(&resolve));
+        request.set_onerror(Some(&reject));
+    });
+    JsFuture::from(promise).await.map_err(js_error)?;
+    request.result().map_err(js_error)
+}
+
+fn js_error(error: impl Into<JsValue>) -> StorageError {
+    StorageError(format!("{:?}", error.into()))
+}
@@ -0,0 +1,54 @@
+use std::collections::BTreeMap;
+
+use sorrow_core::utils::Shared;
+
+use super::{SaveStorage, StorageEvent};
+
+/// Storage that keeps saves in memory, completing every request immediately.
+///
+/// Clones share the same saves, so a clone kept outside the engine can inspect what was written.
+#[derive(Default, Clone)]
+pub struct MemoryStorage {
+    saves: Shared<BTreeMap<String, String>>,
+    events: Shared<Vec<StorageEvent>>,
+}
+
+impl MemoryStorage {
+    pub fn get(&self, key: &str) -> Option<String> {
+        self.saves.borrow().get(key).cloned()
+    }
+
+    pub fn insert(&self, key: &str, data: String) {
+        self.saves.borrow_mut().insert(key.to_string(), data);
+    }
+}
+
+impl SaveStorage for MemoryStorage {
+    fn read(&mut self, key: &str) {
+        let result = Ok(self.get(key));
+        self.events.borrow_mut().push(StorageEvent::Read {
+            key: key.to_string(),
+            result,
+        });
+    }
+
+    fn write(&mut self, key: &str, data: String) {
+        self.insert(key, data);
+        self.events.borrow_mut().push(StorageEvent::Written {
+            key: key.to_string(),
+            result: Ok(()),
+        });
+    }
+
+    fn delete(&mut self, key: &str) {
+        self.saves.borrow_mut().remove(key);
+        self.events.borrow_mut().push(StorageEvent::Deleted {
+            key: key.to_string(),
+            result: Ok(()),
+        });
+    }
+
+    fn poll(&mut self) -> Vec<StorageEvent> {
+        std::mem::take(&mut *self.events.borrow_mut())
+    }
+}
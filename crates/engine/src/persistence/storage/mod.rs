@@ -0,0 +1,54 @@
+#[cfg(feature = "wasm")]
+mod browser;
+mod memory;
+
+#[cfg(feature = "wasm")]
+pub use browser::BrowserStorage;
+pub use memory::MemoryStorage;
+
+use std::fmt;
+
+/// Backend that keeps saves around between sessions.
+///
+/// Requests may complete asynchronously, so their results are collected through
+/// [`SaveStorage::poll`] instead of being returned directly.
+pub trait SaveStorage {
+    /// Starts reading the data stored under `key`.
+    fn read(&mut self, key: &str);
+
+    /// Starts writing `data` under `key`, replacing anything stored there before.
+    fn write(&mut self, key: &str, data: String);
+
+    /// Starts removing whatever is stored under `key`.
+    fn delete(&mut self, key: &str);
+
+    /// Takes the results of the requests that have completed since the last call.
+    fn poll(&mut self) -> Vec<StorageEvent>;
+}
+
+#[derive(Debug)]
+pub enum StorageEvent {
+    Read {
+        key: String,
+        result: Result<Option<String>, StorageError>,
+    },
+    Written {
+        key: String,
+        result: Result<(), StorageError>,
+    },
+    Deleted {
+        key: String,
+        result: Result<(), StorageError>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StorageError(pub String);
+
+impl fmt::Display for StorageError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "storage error: {}", self.0)
+    }
+}
+
+impl std::error::Error for StorageError {}
@@ -0,0 +1,262 @@
+#[cfg(feature = "headless")]
+pub mod playback;
+
+use std::collections::VecDeque;
+
+use bevy::{
+    app::{App, First, Plugin},
+    prelude::*,
+};
+
+use sorrow_core::{
+    communication::EngineMessage,
+    persistence::{Replay, ReplayEntry, ReplayInput, SavePayload, SaveState},
+    state::time::RunningState,
+};
+
+use crate::{
+    io::{self, InputEvent, OutputEvent},
+    persistence::{self, PendingLoad, PendingSave, SaveRequests, SaveTarget},
+    simulation::{
+        calendar::DayTicker,
+        offline::TimeAway,
+        ticker::{ElapsedTicks, Ticker},
+        time::TimeState,
+    },
+};
+
+/// Inputs that a recording holds before it starts over from the current state at the start of the
+/// next in-game day, so that a long session does not keep every input it ever received.
+pub const MAX_RECORDED_INPUTS: usize = 10_000;
+
+/// Inputs received since the last load, tagged with the tick they arrived on.
+///
+/// Once it is full, the recording starts over from the state the world is in when the next day
+/// starts.
+#[derive(Resource, Debug)]
+pub struct Recording {
+    initial: Option<SaveState>,
+    running_state: RunningState,
+    speed: f64,
+    start_tick: u64,
+    entries: Vec<ReplayEntry>,
+}
+
+impl Default for Recording {
+    fn default() -> Self {
+        let time_state = TimeState::default();
+        Self {
+            initial: None,
+            running_state: time_state.running_state,
+            speed: time_state.speed,
+            start_tick: 0,
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl Recording {
+    /// The last `count` inputs recorded since the recording started.
+    pub fn recent(&self, count: usize) -> &[ReplayEntry] {
+        &self.entries[self.entries.len().saturating_sub(count)..]
+    }
+
+    fn record(&mut self, elapsed: &ElapsedTicks, input: ReplayInput) {
+        self.entries.push(ReplayEntry {
+            tick: elapsed.0 - self.start_tick,
+            input,
+        });
+    }
+
+    fn is_full(&self) -> bool {
+        self.entries.len() >= MAX_RECORDED_INPUTS
+    }
+}
+
+/// Present while a [`Replay`] is fed to the simulation in place of the client and the wall clock.
+#[derive(Resource, Debug)]
+pub struct Playback {
+    entries: VecDeque<ReplayEntry>,
+    /// Tick the played back recording started on, once its initial state has been loaded.
+    start_tick: Option<u64>,
+}
+
+impl Playback {
+    /// Number of ticks that ran since the played back recording started.
+    pub fn ticks(&self, elapsed: &ElapsedTicks) -> Option<u64> {
+        self.start_tick.map(|start| elapsed.0 - start)
+    }
+
+    pub fn remaining(&self) -> usize {
+        self.entries.len()
+    }
+}
+
+pub fn is_playing_back(playback: Option<Res<Playback>>) -> bool {
+    playback.is_some()
+}
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Recording>()
+            .add_systems(
+                First,
+                play_back.in_set(io::sets::Inputs).run_if(is_playing_back),
+            )
+            .add_systems(
+                First,
+                (
+                    record_intents
+                        .after(io::sets::Inputs)
+                        .before(persistence::sets::Load),
+                    restart_recording.in_set(persistence::sets::Load),
+                    record_time_away.after(persistence::sets::Load),
+                    request_start_over
+                        .after(record_time_away)
+                        .before(persistence::sets::Save),
+                    (export_replay, start_over)
+                        .chain()
+                        .after(persistence::sets::Save)
+                        .before(persistence::sets::Complete),
+                ),
+            );
+    }
+}
+
+fn play_back(
+    mut playback: ResMut<Playback>,
+    elapsed: Res<ElapsedTicks>,
+    mut inputs: EventWriter<InputEvent>,
+    mut time_away: EventWriter<TimeAway>,
+) {
+    let Some(tick) = playback.ticks(&elapsed) else {
+        return;
+    };
+    while playback.entries.front().is_some_and(|e| e.tick <= tick) {
+        let Some(entry) = playback.entries.pop_front() else {
+            break;
+        };
+        match entry.input {
+            ReplayInput::Intent(intent) => {
+                inputs.send(InputEvent(intent));
+            }
+            ReplayInput::TimeAway { gap_millis } => {
+                time_away.send(TimeAway { gap_millis });
+            }
+        }
+    }
+}
+
+fn record_intents(
+    mut inputs: EventReader<InputEvent>,
+    elapsed: Res<ElapsedTicks>,
+    mut recording: ResMut<Recording>,
+) {
+    for InputEvent(intent) in inputs.read() {
+        recording.record(&elapsed, ReplayInput::Intent(intent.clone()));
+    }
+}
+
+fn restart_recording(
+    load: Res<PendingLoad>,
+    time_state: Res<TimeState>,
+    elapsed: Res<ElapsedTicks>,
+    mut recording: ResMut<Recording>,
+    playback: Option<ResMut<Playback>>,
+) {
+    // Whatever came before the load cannot affect the world after it.
+    *recording = Recording {
+        initial: Some(load.0.clone()),
+        running_state: time_state.running_state,
+        speed: time_state.speed,
+        start_tick: elapsed.0,
+        entries: Vec::new(),
+    };
+
+    if let Some(mut playback) = playback {
+        playback.start_tick.get_or_insert(elapsed.0);
+    }
+}
+
+fn record_time_away(
+    mut time_away: EventReader<TimeAway>,
+    elapsed: Res<ElapsedTicks>,
+    mut recording: ResMut<Recording>,
+) {
+    for TimeAway { gap_millis } in time_away.read() {
+        let input = ReplayInput::TimeAway {
+            gap_millis: *gap_millis,
+        };
+        recording.record(&elapsed, input);
+    }
+}
+
+fn request_start_over(
+    recording: Res<Recording>,
+    day_ticker: Single<&Ticker, With<DayTicker>>,
+    mut requests: ResMut<SaveRequests>,
+) {
+    // Progress into the day is not saved, so a playback that loads the state starts on a new day.
+    if recording.is_full() && day_ticker.just_ticked() {
+        requests.request(SaveTarget::Recording);
+    }
+}
+
+fn start_over(
+    requests: Res<SaveRequests>,
+    save: Res<PendingSave>,
+    time_state: Res<TimeState>,
+    elapsed: Res<ElapsedTicks>,
+    mut recording: ResMut<Recording>,
+) {
+    if !requests.contains(SaveTarget::Recording) {
+        return;
+    }
+
+    // Inputs of the current tick have not been simulated yet, so the state does not include them.
+    let tick = elapsed.0 - recording.start_tick;
+    let entries = std::mem::take(&mut recording.entries)
+        .into_iter()
+        .filter(|entry| entry.tick == tick)
+        .map(|entry| ReplayEntry { tick: 0, ..entry })
+        .collect();
+    *recording = Recording {
+        initial: Some(save.0.clone()),
+        running_state: time_state.running_state,
+        speed: time_state.speed,
+        start_tick: elapsed.0,
+        entries,
+    };
+}
+
+fn export_replay(
+    requests: Res<SaveRequests>,
+    save: Res<PendingSave>,
+    elapsed: Res<ElapsedTicks>,
+    recording: Res<Recording>,
+    mut outputs: EventWriter<OutputEvent>,
+) {
+    if !requests.contains(SaveTarget::Replay) {
+        return;
+    }
+
+    let replay = Replay {
+        initial: recording.initial.as_ref().map(SavePayload::encode),
+        running_state: recording.running_state,
+        speed: recording.speed,
+        entries: recording.entries.clone(),
+        ticks: elapsed.0 - recording.start_tick,
+        outcome: SavePayload::encode(&without_timestamp(&save.0)),
+    };
+    outputs.send(EngineMessage::Replay(replay).into());
+}
+
+/// The state with its wall-clock save time left out, which no playback can reproduce.
+fn without_timestamp(state: &SaveState) -> SaveState {
+    SaveState {
+        saved_at: None,
+        ..state.clone()
+    }
+}
@@ -0,0 +1,152 @@
+use std::fmt;
+
+use bevy::{
+    time::{Time, TimeUpdateStrategy, Virtual},
+    utils::Duration,
+};
+
+use sorrow_core::{
+    communication::{EngineMessage, Intent},
+    persistence::{LoadError, Replay, SavePayload, SaveState},
+};
+
+use crate::{
+    headless::HeadlessEngine,
+    persistence::{storage::MemoryStorage, PendingLoad},
+    runner::HeadlessRunnerPlugin,
+    simulation::{ticker::ElapsedTicks, time::TimeState},
+};
+
+use super::{without_timestamp, Playback};
+
+/// Frames a playback may spend without running a tick or applying an input before giving up.
+const MAX_IDLE_FRAMES: usize = 1_000;
+
+/// How a [`Replay`] ended when played back.
+#[derive(Debug)]
+pub struct PlaybackOutcome {
+    /// State the playback ended in, without its save timestamp.
+    pub state: SaveState,
+    /// Whether the state is bit-identical to the one the recording ended in.
+    pub matches: bool,
+}
+
+#[derive(Debug)]
+pub enum PlaybackError {
+    /// The initial or the final state of the replay could not be decoded.
+    Load(LoadError),
+    /// Time stopped before the recorded inputs or ticks ran out.
+    Stalled { tick: u64, remaining: usize },
+}
+
+impl fmt::Display for PlaybackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlaybackError::Load(error) => write!(f, "could not load replay: {error}"),
+            PlaybackError::Stalled { tick, remaining } => write!(
+                f,
+                "playback stalled on tick {tick} with {remaining} inputs left"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for PlaybackError {}
+
+impl From<LoadError> for PlaybackError {
+    fn from(value: LoadError) -> Self {
+        PlaybackError::Load(value)
+    }
+}
+
+/// Plays the replay back in a fresh headless engine and checks that it reproduces the recording.
+///
+/// The engine is driven one base tick per frame and never reads the wall clock, so every recorded
+/// input is applied before exactly the same tick it originally was.
+pub fn play_back(replay: &Replay) -> Result<PlaybackOutcome, PlaybackError> {
+    let expected = replay.outcome.decode()?;
+    let initial = replay
+        .initial
+        .as_ref()
+        .map(SavePayload::decode)
+        .transpose()?;
+
+    let mut engine = HeadlessEngine::new(
+        HeadlessRunnerPlugin::one_tick_per_update(),
+        MemoryStorage::default(),
+    );
+
+    let world = engine.world_mut();
+    let mut time_state = world.resource_mut::<TimeState>();
+    time_state.running_state = replay.running_state;
+    time_state.speed = replay.speed;
+    let start_tick = world.resource::<ElapsedTicks>().0;
+    world.insert_resource(Playback {
+        entries: replay.entries.iter().cloned().collect(),
+        start_tick: initial.is_none().then_some(start_tick),
+    });
+    if let Some(initial) = initial {
+        world.insert_resource(PendingLoad(initial));
+    }
+    // The inputs recorded on the first tick have to arrive before it runs.
+    update_without_ticks(&mut engine);
+
+    let mut idle_frames = 0;
+    let mut progress = playback_progress(&engine);
+    while progress.0 < replay.ticks {
+        if idle_frames == MAX_IDLE_FRAMES {
+            return Err(PlaybackError::Stalled {
+                tick: progress.0,
+                remaining: progress.1,
+            });
+        }
+
+        // Game speed would run several ticks per frame, and with them inputs would arrive late.
+        engine
+            .world_mut()
+            .resource_mut::<Time<Virtual>>()
+            .set_relative_speed(1.0);
+        engine.update();
+
+        let next = playback_progress(&engine);
+        idle_frames = if next == progress { idle_frames + 1 } else { 0 };
+        progress = next;
+    }
+
+    // Inputs recorded on the last tick arrive in the same frame the state is collected in.
+    engine.send(Intent::Save);
+    update_without_ticks(&mut engine);
+    let state = engine
+        .receive()
+        .into_iter()
+        .find_map(|message| match message {
+            EngineMessage::Saved(payload) => Some(payload),
+            _ => None,
+        })
+        .expect("a save should be answered within the frame")
+        .decode()?;
+    let state = without_timestamp(&state);
+
+    let matches = SavePayload::encode(&state) == SavePayload::encode(&expected);
+    Ok(PlaybackOutcome { state, matches })
+}
+
+/// Ticks run since the playback started, and recorded inputs that were not applied yet.
+fn playback_progress(engine: &HeadlessEngine) -> (u64, usize) {
+    let world = engine.world();
+    let playback = world.resource::<Playback>();
+    let ticks = playback
+        .ticks(world.resource::<ElapsedTicks>())
+        .unwrap_or_default();
+    (ticks, playback.remaining())
+}
+
+fn update_without_ticks(engine: &mut HeadlessEngine) {
+    let world = engine.world_mut();
+    let strategy = world
+        .remove_resource::<TimeUpdateStrategy>()
+        .expect("playback should run on a manual clock");
+    world.insert_resource(TimeUpdateStrategy::ManualDuration(Duration::ZERO));
+    engine.update();
+    engine.world_mut().insert_resource(strategy);
+}
@@ -0,0 +1,86 @@
+use bevy::app::{App, AppExit, Plugin, PluginsState};
+use bevy::time::TimeUpdateStrategy;
+use bevy::utils::{Duration, Instant};
+
+use crate::simulation::ticker::TickRate;
+
+use super::{FramePacing, PacingPlugin};
+
+/// Drives the app from a plain loop on the current thread, without a browser.
+pub struct HeadlessRunnerPlugin {
+    clock: Clock,
+}
+
+enum Clock {
+    /// Time follows the wall clock, with at most one update per frame duration and late frames
+    /// caught up on.
+    Real(Duration),
+    /// Time advances by the same duration on every update, however long the update took.
+    Manual(Duration),
+}
+
+impl HeadlessRunnerPlugin {
+    pub fn real_time(frame: Duration) -> Self {
+        Self {
+            clock: Clock::Real(frame),
+        }
+    }
+
+    pub fn manual(step: Duration) -> Self {
+        Self {
+            clock: Clock::Manual(step),
+        }
+    }
+
+    /// A manual clock where every update runs exactly one base tick at normal speed.
+    pub fn one_tick_per_update() -> Self {
+        Self::manual(Duration::from_secs_f64(
+            TickRate::default().seconds_per_tick,
+        ))
+    }
+}
+
+impl Plugin for HeadlessRunnerPlugin {
+    fn build(&self, app: &mut App) {
+        let frame = match self.clock {
+            Clock::Real(frame) => {
+                app.add_plugins(PacingPlugin);
+                frame
+            }
+            Clock::Manual(step) => {
+                app.insert_resource(TimeUpdateStrategy::ManualDuration(step));
+                Duration::ZERO
+            }
+        };
+
+        app.set_runner(move |app| run_loop(app, frame));
+    }
+}
+
+fn run_loop(mut app: App, frame: Duration) -> AppExit {
+    let plugins_state = app.plugins_state();
+    if plugins_state != PluginsState::Cleaned {
+        while app.plugins_state() == PluginsState::Adding {}
+        app.finish();
+        app.cleanup();
+    }
+
+    loop {
+        let start_time = Instant::now();
+
+        app.update();
+
+        if let Some(exit) = app.should_exit() {
+            return exit;
+        }
+
+        let frame = app
+            .world()
+            .get_resource::<FramePacing>()
+            .map_or(frame, |pacing| pacing.cadence(frame));
+        let exe_time = start_time.elapsed();
+        if exe_time < frame {
+            std::thread::sleep(frame - exe_time);
+        }
+    }
+}
@@ -0,0 +1,20 @@
+#[cfg(feature = "headless")]
+mod headless;
+mod pacing;
+#[cfg(feature = "wasm")]
+mod timeout;
+
+#[cfg(feature = "headless")]
+pub use headless::HeadlessRunnerPlugin;
+pub use pacing::{FramePacing, PacingPlugin};
+#[cfg(feature = "wasm")]
+pub use timeout::TimeoutRunnerPlugin;
+
+use bevy::prelude::Res;
+
+/// Whether frames follow the wall clock, as opposed to a manual clock that advances by a fixed
+/// step however long frames take.
+pub fn is_on_wall_clock(pacing: Option<Res<FramePacing>>) -> bool {
+    // Only runners that follow the wall clock pace their frames.
+    pacing.is_some()
+}
@@ -0,0 +1,233 @@
+//! Pacing of frames run on the wall clock, so that frames which come late, such as in throttled
+//! browser tabs, are caught up on instead of slowing the game down.
+
+use bevy::{
+    app::{App, First, Last, Plugin, Startup},
+    prelude::*,
+    time::{TimeSystem, TimeUpdateStrategy},
+    utils::{Duration, Instant},
+};
+
+use sorrow_core::{
+    communication::{EngineUpdate, PacingTransport},
+    diagnostics::FrameTiming,
+};
+
+use crate::{
+    io::{snapshot::PendingSnapshot, UpdatedEvent},
+    schedules::BufferChanges,
+    simulation::offline,
+};
+
+/// Most wall-clock time fed into the fixed timestep in a single frame.
+///
+/// Anything above this is owed and fed in over the following frames, so a slow frame is caught up
+/// on without one frame running all of the missed ticks at once.
+pub const FRAME_BUDGET: Duration = Duration::from_secs(2);
+
+/// Time between frames while the game is hidden, since browsers throttle hidden timers anyway.
+pub const HIDDEN_CADENCE: Duration = Duration::from_secs(1);
+
+/// Time owed above which the simulation counts as catching up.
+const CATCH_UP_THRESHOLD: Duration = Duration::from_millis(500);
+
+/// Gaps this long are caught up on by the offline fast-forward instead.
+const MAX_OWED: Duration = Duration::from_millis(offline::MIN_GAP_MILLIS as u64);
+
+/// Paces frames run on the wall clock, keeping track of time that still has to be caught up on.
+///
+/// Replaces the time update strategy every frame, so it does not go together with a manual clock.
+#[derive(Resource, Debug, Default)]
+pub struct FramePacing {
+    /// Whether the game is hidden from view, so frames can be run at [`HIDDEN_CADENCE`].
+    pub hidden: bool,
+    /// Wall-clock time that passed but was not fed into the fixed timestep yet.
+    owed: Duration,
+    frame_start: Option<Instant>,
+    /// Wall-clock time between the starts of the last two frames.
+    gap: Duration,
+    /// Wall-clock time fed into the fixed timestep in the last frame.
+    fed: Duration,
+    /// How long the last frame took to run.
+    busy: Duration,
+}
+
+impl FramePacing {
+    /// Time to wait between the starts of frames, given the cadence while the game is visible.
+    pub fn cadence(&self, visible: Duration) -> Duration {
+        if self.hidden {
+            visible.max(HIDDEN_CADENCE)
+        } else {
+            visible
+        }
+    }
+
+    pub fn is_catching_up(&self) -> bool {
+        self.owed >= CATCH_UP_THRESHOLD
+    }
+
+    pub fn timing(&self) -> FrameTiming {
+        FrameTiming {
+            gap_millis: self.gap.as_secs_f64() * 1000.0,
+            fed_millis: self.fed.as_secs_f64() * 1000.0,
+            busy_millis: self.busy.as_secs_f64() * 1000.0,
+            owed_millis: self.owed.as_secs_f64() * 1000.0,
+        }
+    }
+
+    /// Starts a frame at `now` and returns the wall-clock time to feed into it.
+    fn start_frame(&mut self, now: Instant) -> Duration {
+        let gap = self
+            .frame_start
+            .replace(now)
+            .map_or(Duration::ZERO, |start| now - start);
+
+        // A suspension is caught up on in bulk, so only shorter gaps are owed here.
+        let incoming = if gap >= MAX_OWED { Duration::ZERO } else { gap };
+        let total = self.owed + incoming;
+        if total > MAX_OWED {
+            tracing::debug!(
+                "Dropping {:?} the simulation fell behind by",
+                total - MAX_OWED
+            );
+        }
+        let total = total.min(MAX_OWED);
+
+        let fed = total.min(FRAME_BUDGET);
+        self.owed = total - fed;
+        self.gap = gap;
+        self.fed = fed;
+        fed
+    }
+}
+
+pub struct PacingPlugin;
+
+impl Plugin for PacingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<FramePacing>()
+            .add_systems(Startup, widen_max_delta)
+            .add_systems(First, start_frame.before(TimeSystem))
+            .add_systems(Last, end_frame)
+            .add_systems(BufferChanges, detect_pacing_changes)
+            .add_systems(
+                BufferChanges,
+                snapshot_pacing.run_if(resource_exists::<PendingSnapshot>),
+            );
+    }
+}
+
+fn widen_max_delta(mut time: ResMut<Time<Virtual>>) {
+    // Virtual time would otherwise clamp away the catch-up fed into a frame.
+    time.set_max_delta(FRAME_BUDGET);
+}
+
+fn start_frame(mut pacing: ResMut<FramePacing>, mut strategy: ResMut<TimeUpdateStrategy>) {
+    let fed = pacing.start_frame(Instant::now());
+    *strategy = TimeUpdateStrategy::ManualDuration(fed);
+}
+
+fn end_frame(mut pacing: ResMut<FramePacing>) {
+    if let Some(start) = pacing.frame_start {
+        pacing.busy = start.elapsed();
+    }
+}
+
+fn detect_pacing_changes(
+    pacing: Res<FramePacing>,
+    mut reported: Local<Option<(bool, u64)>>,
+    mut updates: EventWriter<UpdatedEvent>,
+) {
+    let current = (pacing.is_catching_up(), pacing.owed.as_secs());
+    let previous = reported.replace(current);
+    if previous == Some(current) {
+        return;
+    }
+
+    let (catching_up, behind_seconds) = current;
+    updates.send(
+        EngineUpdate::PacingChanged(PacingTransport {
+            catching_up: previous
+                .is_none_or(|(was, _)| was != catching_up)
+                .then_some(catching_up),
+            behind_seconds: previous
+                .is_none_or(|(_, was)| was != behind_seconds)
+                .then_some(behind_seconds),
+        })
+        .into(),
+    );
+}
+
+fn snapshot_pacing(pacing: Res<FramePacing>, mut snapshot: ResMut<PendingSnapshot>) {
+    snapshot.0.pacing = PacingTransport {
+        catching_up: Some(pacing.is_catching_up()),
+        behind_seconds: Some(pacing.owed.as_secs()),
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const FRAME: Duration = Duration::from_millis(20);
+
+    /// Starts frames after each of the gaps and returns the time fed into each of them.
+    fn feed(pacing: &mut FramePacing, gaps: &[Duration]) -> Vec<Duration> {
+        let mut now = pacing.frame_start.unwrap_or_else(Instant::now);
+        gaps.iter()
+            .map(|gap| {
+                now += *gap;
+                pacing.start_frame(now)
+            })
+            .collect()
+    }
+
+    fn started() -> FramePacing {
+        let mut pacing = FramePacing::default();
+        assert_eq!(pacing.start_frame(Instant::now()), Duration::ZERO);
+        pacing
+    }
+
+    #[test]
+    fn feeds_frames_that_arrive_on_time_in_full() {
+        let mut pacing = started();
+        assert_eq!(feed(&mut pacing, &[FRAME, FRAME]), [FRAME, FRAME]);
+        assert_eq!(pacing.owed, Duration::ZERO);
+        assert!(!pacing.is_catching_up());
+    }
+
+    #[test]
+    fn owes_what_does_not_fit_into_the_budget() {
+        let mut pacing = started();
+        let late = FRAME_BUDGET + Duration::from_secs(1);
+        assert_eq!(feed(&mut pacing, &[late]), [FRAME_BUDGET]);
+        assert_eq!(pacing.owed, Duration::from_secs(1));
+        assert!(pacing.is_catching_up());
+
+        assert_eq!(
+            feed(&mut pacing, &[FRAME]),
+            [Duration::from_secs(1) + FRAME]
+        );
+        assert_eq!(pacing.owed, Duration::ZERO);
+        assert!(!pacing.is_catching_up());
+    }
+
+    #[test]
+    fn leaves_suspensions_to_the_offline_catch_up() {
+        let mut pacing = started();
+        assert_eq!(feed(&mut pacing, &[MAX_OWED]), [Duration::ZERO]);
+        assert_eq!(pacing.owed, Duration::ZERO);
+        assert_eq!(pacing.gap, MAX_OWED);
+    }
+
+    #[test]
+    fn owes_no_more_than_a_suspension() {
+        let mut pacing = started();
+        let late = MAX_OWED - FRAME;
+        assert_eq!(
+            feed(&mut pacing, &[late, late]),
+            [FRAME_BUDGET, FRAME_BUDGET]
+        );
+        assert_eq!(pacing.owed, MAX_OWED - FRAME_BUDGET);
+    }
+}
@@ -6,6 +6,8 @@ use wasm_bindgen::prelude::Closure;
 use wasm_bindgen::JsCast;
 use web_sys::{js_sys, WorkerGlobalScope};
 
+use super::{FramePacing, PacingPlugin};
+
 pub struct TimeoutRunnerPlugin {
     duration: Duration,
 }
@@ -18,8 +20,9 @@ impl TimeoutRunnerPlugin {
 
 impl Plugin for TimeoutRunnerPlugin {
     fn build(&self, app: &mut App) {
-        let duration = Some(self.duration);
+        let duration = self.duration;
 
+        app.add_plugins(PacingPlugin);
         app.set_runner(move |mut app| {
             let plugins_state = app.plugins_state();
             if plugins_state != PluginsState::Cleaned {
@@ -28,7 +31,7 @@ impl Plugin for TimeoutRunnerPlugin {
                 app.cleanup();
             }
 
-            let mut tick = move |wait: Option<Duration>| -> Result<Option<Duration>, AppExit> {
+            let mut tick = move |visible: Duration| -> Result<Option<Duration>, AppExit> {
                 let start_time = Instant::now();
 
                 app.update();
@@ -39,11 +42,12 @@ impl Plugin for TimeoutRunnerPlugin {
 
                 let end_time = Instant::now();
 
-                if let Some(wait) = wait {
-                    let exe_time = end_time - start_time;
-                    if exe_time < wait {
-                        return Ok(Some(wait - exe_time));
-                    }
+                // Timers are throttled in hidden pages, so frames are spaced out to match and the
+                // time in between is caught up on instead.
+                let wait = app.world().resource::<FramePacing>().cadence(visible);
+                let exe_time = end_time - start_time;
+                if exe_time < wait {
+                    return Ok(Some(wait - exe_time));
                 }
 
                 Ok(None)
@@ -7,10 +7,16 @@ use bevy::{
 
 use sorrow_core::{
     communication::{BuildingTransport, EngineUpdate},
+    persistence::BuildingState,
     state::{buildings::BuildingKind, KeyIter},
 };
 
-use crate::{index::LookupIndexPlugin, io::UpdatedEvent, schedules::BufferChanges};
+use crate::{
+    index::LookupIndexPlugin,
+    io::{snapshot::PendingSnapshot, UpdatedEvent},
+    persistence::{self, PendingLoad, PendingSave},
+    schedules::BufferChanges,
+};
 
 #[derive(Component, Clone, Copy, Hash, PartialEq, Eq, Debug)]
 pub struct Building(pub BuildingKind);
@@ -42,7 +48,13 @@ impl Plugin for BuildingsPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(LookupIndexPlugin::<Building>::new())
             .add_systems(Startup, spawn_buildings)
-            .add_systems(BufferChanges, detect_building_changes);
+            .add_systems(First, load_buildings.in_set(persistence::sets::Load))
+            .add_systems(First, save_buildings.in_set(persistence::sets::Save))
+            .add_systems(BufferChanges, detect_building_changes)
+            .add_systems(
+                BufferChanges,
+                snapshot_buildings.run_if(resource_exists::<PendingSnapshot>),
+            );
     }
 }
 
@@ -50,6 +62,28 @@ fn spawn_buildings(mut commands: Commands) {
     commands.spawn_batch(BuildingKind::key_iter().map(|k| (Building(k), Level(0))));
 }
 
+fn load_buildings(
+    mut commands: Commands,
+    load: Res<PendingLoad>,
+    buildings: Query<Entity, With<Building>>,
+) {
+    for entity in buildings.iter() {
+        commands.entity(entity).despawn();
+    }
+    let levels = BuildingKind::key_iter().map(|k| {
+        let state = load.0.buildings.get(&k).copied().unwrap_or_default();
+        (Building(k), Level(state.level))
+    });
+    commands.spawn_batch(levels.collect::<Vec<_>>());
+}
+
+fn save_buildings(buildings: Query<(&Building, &Level)>, mut save: ResMut<PendingSave>) {
+    save.0.buildings = buildings
+        .iter()
+        .map(|(kind, level)| (kind.0, BuildingState { level: level.0 }))
+        .collect();
+}
+
 fn detect_building_changes(
     buildings: Query<(&Building, &Level), Changed<Level>>,
     mut updates: EventWriter<UpdatedEvent>,
@@ -65,3 +99,13 @@ fn detect_building_changes(
         updates.send(EngineUpdate::BuildingsChanged(transport).into());
     }
 }
+
+fn snapshot_buildings(
+    buildings: Query<(&Building, &Level)>,
+    mut snapshot: ResMut<PendingSnapshot>,
+) {
+    let transport = &mut snapshot.0.buildings;
+    for (kind, level) in buildings.iter() {
+        *transport.levels.get_state_mut(&kind.0) = Some(level.0);
+    }
+}
@@ -5,13 +5,19 @@ use bevy::{
 
 use sorrow_core::{
     communication::{CalendarTransport, EngineUpdate},
+    persistence::CalendarState,
     state::calendar::SeasonKind,
 };
 
-use crate::{io::UpdatedEvent, schedules::BufferChanges, simulation::ticker::Ticker};
+use crate::{
+    io::{snapshot::PendingSnapshot, UpdatedEvent},
+    persistence::{self, PendingLoad, PendingSave},
+    schedules::BufferChanges,
+    simulation::ticker::Ticker,
+};
 
 #[derive(Component)]
-struct DayTicker;
+pub struct DayTicker;
 
 #[derive(Component)]
 pub struct Calendar;
@@ -37,16 +43,54 @@ pub struct CalendarPlugin;
 impl Plugin for CalendarPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(Startup, spawn)
+            .add_systems(First, load_calendar.in_set(persistence::sets::Load))
+            .add_systems(First, save_calendar.in_set(persistence::sets::Save))
             .add_systems(FixedUpdate, advance_calendar.in_set(sets::Main))
-            .add_systems(BufferChanges, detect_calendar_changes);
+            .add_systems(BufferChanges, detect_calendar_changes)
+            .add_systems(
+                BufferChanges,
+                snapshot_calendar.run_if(resource_exists::<PendingSnapshot>),
+            );
     }
 }
 
 fn spawn(mut cmd: Commands) {
-    cmd.spawn((Calendar, Year(0), Season(SeasonKind::Spring), Day(0)));
+    spawn_calendar(&mut cmd, CalendarState::default());
     cmd.spawn((DayTicker, Ticker::from_scale(10)));
 }
 
+fn spawn_calendar(cmd: &mut Commands, state: CalendarState) {
+    cmd.spawn((
+        Calendar,
+        Year(state.year),
+        Season(state.season),
+        Day(state.day),
+    ));
+}
+
+fn load_calendar(
+    mut cmd: Commands,
+    load: Res<PendingLoad>,
+    calendar: Query<Entity, With<Calendar>>,
+) {
+    for entity in calendar.iter() {
+        cmd.entity(entity).despawn();
+    }
+    spawn_calendar(&mut cmd, load.0.calendar);
+}
+
+fn save_calendar(
+    calendar: Single<(&Day, &Season, &Year), With<Calendar>>,
+    mut save: ResMut<PendingSave>,
+) {
+    let (day, season, year) = *calendar;
+    save.0.calendar = CalendarState {
+        day: day.0,
+        season: season.0,
+        year: year.0,
+    };
+}
+
 fn advance_calendar(
     day_ticker: Single<&Ticker, With<DayTicker>>,
     mut calendar: Single<(&mut Day, &mut Season, &mut Year), With<Calendar>>,
@@ -55,7 +99,12 @@ fn advance_calendar(
         return;
     }
 
-    let day = &mut calendar.0;
+    let (day, season, year) = &mut *calendar;
+    advance_day(day, season, year);
+}
+
+/// Moves the calendar to the next day and returns whether a new season started.
+pub fn advance_day(day: &mut Mut<Day>, season: &mut Mut<Season>, year: &mut Mut<Year>) -> bool {
     let mut is_new_season = false;
     if day.0 == 99 {
         day.0 = 0;
@@ -66,7 +115,6 @@ fn advance_calendar(
 
     let mut is_new_year = false;
     if is_new_season {
-        let season = &mut calendar.1;
         if season.0 == SeasonKind::Winter {
             is_new_year = true;
         }
@@ -80,9 +128,10 @@ fn advance_calendar(
     }
 
     if is_new_year {
-        let year = &mut calendar.2;
         year.0 += 1;
     }
+
+    is_new_season
 }
 
 fn detect_calendar_changes(
@@ -115,3 +164,15 @@ fn detect_calendar_changes(
         }
     }
 }
+
+fn snapshot_calendar(
+    calendar: Single<(&Day, &Season, &Year), With<Calendar>>,
+    mut snapshot: ResMut<PendingSnapshot>,
+) {
+    let (day, season, year) = *calendar;
+    snapshot.0.calendar = CalendarTransport {
+        day: Some(day.0),
+        season: Some(season.0),
+        year: Some(year.0),
+    };
+}
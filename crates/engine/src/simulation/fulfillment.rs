@@ -1,14 +1,17 @@
 use bevy::{
-    app::{FixedPostUpdate, Plugin, Startup},
+    app::{First, FixedPostUpdate, Plugin, Startup},
+    hierarchy::DespawnRecursiveExt,
     prelude::{
-        BuildChildren, Changed, ChildBuild, Children, Commands, Component, DetectChanges,
-        EventWriter, IntoSystemConfigs, ParamSet, Parent, Query, Ref, With,
+        resource_exists, BuildChildren, Changed, ChildBuild, Children, Commands, Component,
+        DetectChanges, Entity, EventWriter, IntoSystemConfigs, ParamSet, Parent, Query, Ref, Res,
+        ResMut, With,
     },
     utils::HashMap,
 };
 
 use sorrow_core::{
     communication::{EngineUpdate, FulfillmentTransport},
+    persistence::SaveState,
     state::{
         buildings::{BUILDING_PRICE_RATIOS, BUILDING_UNLOCK_RATIOS},
         recipes::{
@@ -22,7 +25,8 @@ use sorrow_core::{
 
 use crate::{
     index::{IndexedQuery, LookupIndexPlugin},
-    io::UpdatedEvent,
+    io::{snapshot::PendingSnapshot, UpdatedEvent},
+    persistence::{self, PendingLoad, PendingSave},
     schedules::BufferChanges,
     simulation::resources::Capacity,
 };
@@ -74,6 +78,8 @@ impl Plugin for FulfillmentPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.add_plugins(LookupIndexPlugin::<Recipe>::new())
             .add_systems(Startup, spawn_recipes)
+            .add_systems(First, load_recipes.in_set(persistence::sets::Load))
+            .add_systems(First, save_recipes.in_set(persistence::sets::Save))
             .add_systems(
                 FixedPostUpdate,
                 (
@@ -84,46 +90,84 @@ impl Plugin for FulfillmentPlugin {
                     .chain()
                     .in_set(sets::Recalculate),
             )
-            .add_systems(BufferChanges, detect_fulfillment_changes);
+            .add_systems(BufferChanges, detect_fulfillment_changes)
+            .add_systems(
+                BufferChanges,
+                snapshot_fulfillments.run_if(resource_exists::<PendingSnapshot>),
+            );
     }
 }
 
 fn spawn_recipes(mut cmd: Commands) {
+    let state = SaveState::default();
     for recipe in RecipeKind::key_iter() {
-        let mut spawned = cmd.spawn(Recipe(recipe));
-
-        match recipe {
-            RecipeKind::Crafting(crafting_recipe_kind) => {
-                let ResourceAmount(resource, base_amount) = RECIPE_CRAFTED_RESOURCES
-                    .get(&crafting_recipe_kind)
-                    .expect("recipe did not have a crafting result entry");
-                spawned.with_child((CraftedResource(*resource), CraftedAmount(*base_amount)));
-            }
-            RecipeKind::Building(building_kind) => {
-                let price_ratio = BUILDING_PRICE_RATIOS
-                    .get(&building_kind)
-                    .expect("building recipe did not have a price ratio entry");
-                spawned.insert(PriceRatio(*price_ratio));
-
-                if let Some(unlock_ratio) = BUILDING_UNLOCK_RATIOS.get(&building_kind) {
-                    spawned.insert(UnlockRatio(*unlock_ratio));
-                }
-            }
+        spawn_recipe(&mut cmd, recipe, &state);
+    }
+}
+
+fn spawn_recipe(cmd: &mut Commands, recipe: RecipeKind, state: &SaveState) {
+    let mut spawned = cmd.spawn(Recipe(recipe));
+
+    let mut level = 0;
+    let mut price_ratio = None;
+    match recipe {
+        RecipeKind::Crafting(crafting_recipe_kind) => {
+            let ResourceAmount(resource, base_amount) = RECIPE_CRAFTED_RESOURCES
+                .get(&crafting_recipe_kind)
+                .expect("recipe did not have a crafting result entry");
+            spawned.with_child((CraftedResource(*resource), CraftedAmount(*base_amount)));
         }
+        RecipeKind::Building(building_kind) => {
+            let ratio = BUILDING_PRICE_RATIOS
+                .get(&building_kind)
+                .expect("building recipe did not have a price ratio entry");
+            spawned.insert(PriceRatio(*ratio));
+            price_ratio = Some(*ratio);
+            level = state
+                .buildings
+                .get(&building_kind)
+                .map(|b| b.level)
+                .unwrap_or_default();
 
-        spawned.with_children(|b| {
-            for ResourceAmount(resource, base_amount) in RECIPE_INGREDIENTS
-                .get(&recipe)
-                .expect("recipe did not have an ingredients entry")
-            {
-                b.spawn((
-                    Ingredient(*resource),
-                    BaseAmount(*base_amount),
-                    RequiredAmount(*base_amount),
-                ));
+            if let Some(unlock_ratio) = BUILDING_UNLOCK_RATIOS.get(&building_kind) {
+                let unlocked = state.unlocked_recipes.contains(&recipe);
+                spawned.insert((UnlockRatio(*unlock_ratio), super::Unlocked(unlocked)));
             }
-        });
+        }
     }
+
+    spawned.with_children(|b| {
+        for ResourceAmount(resource, base_amount) in RECIPE_INGREDIENTS
+            .get(&recipe)
+            .expect("recipe did not have an ingredients entry")
+        {
+            let required_amount = match price_ratio {
+                Some(ratio) => logic::required_amount(*base_amount, ratio, level),
+                None => *base_amount,
+            };
+            b.spawn((
+                Ingredient(*resource),
+                BaseAmount(*base_amount),
+                RequiredAmount(required_amount),
+            ));
+        }
+    });
+}
+
+fn load_recipes(mut cmd: Commands, load: Res<PendingLoad>, recipes: Query<Entity, With<Recipe>>) {
+    for entity in recipes.iter() {
+        cmd.entity(entity).despawn_recursive();
+    }
+    for recipe in RecipeKind::key_iter() {
+        spawn_recipe(&mut cmd, recipe, &load.0);
+    }
+}
+
+fn save_recipes(recipes: Query<(&Recipe, &super::Unlocked)>, mut save: ResMut<PendingSave>) {
+    save.0.unlocked_recipes = recipes
+        .iter()
+        .filter_map(|(recipe, unlocked)| unlocked.0.then_some(recipe.0))
+        .collect();
 }
 
 fn recalculate_recipe_costs(
@@ -135,7 +179,7 @@ fn recalculate_recipe_costs(
         let (ratio, ingredient_entities) = recipes.item(Recipe(RecipeKind::Building(building.0)));
         let mut amounts = amounts_query.iter_many_mut(ingredient_entities);
         while let Some((mut required_amount, base_amount)) = amounts.fetch_next() {
-            required_amount.0 = base_amount.0 * (ratio.0.powi(level.0 as i32));
+            required_amount.0 = logic::required_amount(base_amount.0, ratio.0, level.0);
         }
     }
 }
@@ -256,3 +300,28 @@ fn detect_fulfillment_changes(
         updates.send(EngineUpdate::FulfillmentsChanged(transport).into());
     }
 }
+
+fn snapshot_fulfillments(
+    recipes: Query<(&Recipe, &Fulfillment)>,
+    ingredients: Query<(&Ingredient, &RequiredAmount, &Parent)>,
+    mut snapshot: ResMut<PendingSnapshot>,
+) {
+    let transport = &mut snapshot.0.fulfillments;
+    for (recipe, fulfillment) in recipes.iter() {
+        *transport.fulfillments.get_state_mut(&recipe.0) = Some(fulfillment.0);
+    }
+    for (ingredient, required_amount, parent) in ingredients.iter() {
+        let (recipe, _) = recipes
+            .get(**parent)
+            .expect("Could not find recipe for ingredient");
+        *transport
+            .required_amounts
+            .get_state_mut(&(recipe.0, ingredient.0)) = Some(required_amount.0);
+    }
+}
+
+mod logic {
+    pub fn required_amount(base_amount: f64, price_ratio: f64, level: u32) -> f64 {
+        base_amount * price_ratio.powi(level as i32)
+    }
+}
@@ -1,8 +1,10 @@
 pub mod buildings;
 pub mod calendar;
 pub mod fulfillment;
+pub mod offline;
 pub mod resources;
 pub mod ticker;
+pub mod time;
 pub mod work_orders;
 
 use bevy::app::{App, FixedPostUpdate, FixedUpdate, Plugin};
@@ -11,8 +13,10 @@ use bevy::prelude::*;
 use buildings::BuildingsPlugin;
 use calendar::CalendarPlugin;
 use fulfillment::FulfillmentPlugin;
+use offline::OfflinePlugin;
 use resources::ResourcesPlugin;
 use ticker::TickerPlugin;
+use time::TimeControlPlugin;
 use work_orders::WorkOrdersPlugin;
 
 #[derive(Component, Default, Debug, Clone, Copy, PartialEq, Eq)]
@@ -22,22 +26,30 @@ pub struct SimulationPlugin;
 
 impl Plugin for SimulationPlugin {
     fn build(&self, app: &mut App) {
-        app.add_plugins(TickerPlugin)
+        app.add_plugins(TimeControlPlugin)
+            .add_plugins(TickerPlugin)
             .add_plugins(CalendarPlugin)
             .add_plugins(WorkOrdersPlugin)
             .add_plugins(ResourcesPlugin)
             .add_plugins(BuildingsPlugin)
             .add_plugins(FulfillmentPlugin)
+            .add_plugins(OfflinePlugin)
             .configure_sets(
                 FixedUpdate,
                 (
+                    offline::sets::Main,
                     ticker::sets::Main,
                     calendar::sets::Main,
                     resources::sets::Prepare,
                     work_orders::sets::Main,
                     resources::sets::Commit,
                 )
-                    .chain(),
+                    .chain()
+                    .run_if(time::is_running),
+            )
+            .configure_sets(
+                FixedUpdate,
+                work_orders::sets::Queue.before(work_orders::sets::Main),
             )
             .configure_sets(
                 FixedPostUpdate,
@@ -0,0 +1,205 @@
+use bevy::{
+    app::{App, First, FixedUpdate, Plugin},
+    prelude::*,
+};
+
+use sorrow_core::{
+    communication::{EngineMessage, OfflineSummary},
+    utils::unix_millis,
+};
+
+use crate::{
+    io::OutputEvent,
+    persistence::{self, PendingLoad, PendingSave},
+    replay::{self, Playback},
+    runner,
+};
+
+use super::{
+    calendar::{self, Calendar, Day, DayTicker, Season, Year},
+    resources::{logic, Amount, Capacity, Credit, Debit, Delta, Resource},
+    ticker::{TickRate, Ticker},
+    time,
+};
+
+/// Gaps between frames shorter than this are left to the fixed timestep.
+pub const MIN_GAP_MILLIS: i64 = 10_000;
+
+/// Number of base ticks applied at once while catching up.
+const BATCH_TICKS: u64 = 1_000;
+
+pub mod sets {
+    use bevy::prelude::SystemSet;
+
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Main;
+}
+
+/// Wall-clock time that passed without the simulation running, to be caught up on.
+#[derive(Event, Debug, Clone, Copy)]
+pub struct TimeAway {
+    pub gap_millis: i64,
+}
+
+#[derive(Resource, Debug)]
+struct WallClock {
+    last_frame: i64,
+}
+
+/// Time that passed without the simulation running, and what happened while catching up on it.
+#[derive(Resource, Debug, Default)]
+struct OfflineProgress {
+    remaining_ticks: u64,
+    summary: OfflineSummary,
+}
+
+impl OfflineProgress {
+    fn add_gap(&mut self, gap_millis: i64, tick_rate: &TickRate) {
+        let seconds = gap_millis as f64 / 1000.0;
+        self.remaining_ticks += (seconds / tick_rate.seconds_per_tick) as u64;
+        self.summary.elapsed_seconds += seconds;
+    }
+
+    fn remaining_millis(&self, tick_rate: &TickRate) -> i64 {
+        (self.remaining_ticks as f64 * tick_rate.seconds_per_tick * 1000.0) as i64
+    }
+}
+
+fn is_catching_up(progress: Res<OfflineProgress>) -> bool {
+    progress.remaining_ticks > 0
+}
+
+pub struct OfflinePlugin;
+
+impl Plugin for OfflinePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(WallClock {
+            last_frame: unix_millis(),
+        })
+        .init_resource::<OfflineProgress>()
+        .add_event::<TimeAway>()
+        .add_systems(
+            First,
+            // A manual clock stands still between frames however long they take, so a slow frame
+            // is not a suspension there.
+            detect_suspension
+                .run_if(runner::is_on_wall_clock)
+                .run_if(not(replay::is_playing_back)),
+        )
+        .add_systems(First, catch_up_after_load.in_set(persistence::sets::Load))
+        .add_systems(First, add_time_away.after(persistence::sets::Load))
+        .add_systems(First, save_timestamp.in_set(persistence::sets::Save))
+        .add_systems(
+            FixedUpdate,
+            fast_forward.in_set(sets::Main).run_if(is_catching_up),
+        );
+    }
+}
+
+fn detect_suspension(
+    mut clock: ResMut<WallClock>,
+    time_state: Res<time::TimeState>,
+    mut time_away: EventWriter<TimeAway>,
+) {
+    let now = unix_millis();
+    let gap = now - clock.last_frame;
+    clock.last_frame = now;
+
+    if gap >= MIN_GAP_MILLIS && time_state.is_running() {
+        tracing::info!("Catching up on {gap}ms spent suspended.");
+        time_away.send(TimeAway { gap_millis: gap });
+    }
+}
+
+fn catch_up_after_load(
+    load: Res<PendingLoad>,
+    playback: Option<Res<Playback>>,
+    mut progress: ResMut<OfflineProgress>,
+    mut time_away: EventWriter<TimeAway>,
+) {
+    *progress = OfflineProgress::default();
+
+    // A playback brings its own time away, recorded when the load first happened.
+    if playback.is_some() {
+        return;
+    }
+    if let Some(saved_at) = load.0.saved_at {
+        let gap = unix_millis() - saved_at;
+        if gap >= MIN_GAP_MILLIS {
+            tracing::info!("Catching up on {gap}ms since the save was made.");
+            time_away.send(TimeAway { gap_millis: gap });
+        }
+    }
+}
+
+fn add_time_away(
+    mut time_away: EventReader<TimeAway>,
+    mut progress: ResMut<OfflineProgress>,
+    tick_rate: Res<TickRate>,
+) {
+    for TimeAway { gap_millis } in time_away.read() {
+        progress.add_gap(*gap_millis, &tick_rate);
+    }
+}
+
+fn save_timestamp(
+    progress: Res<OfflineProgress>,
+    tick_rate: Res<TickRate>,
+    mut save: ResMut<PendingSave>,
+) {
+    // Time that has not been caught up on yet is still owed to whoever loads this save.
+    save.0.saved_at = Some(unix_millis() - progress.remaining_millis(&tick_rate));
+}
+
+fn fast_forward(
+    mut progress: ResMut<OfflineProgress>,
+    mut tickers: Query<(&mut Ticker, Has<DayTicker>)>,
+    mut calendar: Single<(&mut Day, &mut Season, &mut Year), With<Calendar>>,
+    mut resources: Query<(&Resource, &mut Amount, &Delta, Option<&Capacity>)>,
+    mut outputs: EventWriter<OutputEvent>,
+) {
+    let ticks = progress.remaining_ticks.min(BATCH_TICKS);
+    progress.remaining_ticks -= ticks;
+
+    let mut days = 0;
+    for (mut ticker, is_day_ticker) in tickers.iter_mut() {
+        let passed = ticker.fast_forward(ticks as f64);
+        if is_day_ticker {
+            days = passed;
+        }
+    }
+
+    let (day, season, year) = &mut *calendar;
+    let start_year = year.0;
+    for _ in 0..days {
+        if calendar::advance_day(day, season, year) {
+            progress.summary.seasons += 1;
+        }
+    }
+    progress.summary.days += days;
+    progress.summary.years += (year.0 - start_year) as u64;
+
+    for (kind, mut amount, delta, capacity) in resources.iter_mut() {
+        let change = f64::from(*delta) * ticks as f64;
+        let (debit, credit) = if change >= 0.0 {
+            (Debit(change), Credit(0.0))
+        } else {
+            (Debit(0.0), Credit(-change))
+        };
+        let new_amount = logic::total(&amount, &debit, &credit, capacity);
+        let difference = new_amount - amount.0;
+        if difference.abs() > f64::EPSILON {
+            amount.0 = new_amount;
+            *progress.summary.resources.entry(kind.0).or_default() += difference;
+        }
+    }
+
+    if progress.remaining_ticks == 0 {
+        let progress = std::mem::take(&mut *progress);
+        let mut summary = progress.summary;
+        summary
+            .resources
+            .retain(|_, change| change.abs() > f64::EPSILON);
+        outputs.send(EngineMessage::OfflineProgress(summary).into());
+    }
+}
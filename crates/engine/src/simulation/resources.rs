@@ -7,6 +7,7 @@ use bevy::{
 
 use sorrow_core::{
     communication::{EngineUpdate, ResourceTransport},
+    persistence::ResourceState,
     state::{
         buildings::BuildingKind,
         recipes::CraftingRecipeKind,
@@ -17,7 +18,8 @@ use sorrow_core::{
 
 use crate::{
     index::{IndexedQuery, LookupIndexPlugin},
-    io::UpdatedEvent,
+    io::{snapshot::PendingSnapshot, UpdatedEvent},
+    persistence::{self, PendingLoad, PendingSave},
     schedules::BufferChanges,
 };
 
@@ -128,6 +130,8 @@ impl Plugin for ResourcesPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.add_plugins(LookupIndexPlugin::<Resource>::new())
             .add_systems(Startup, spawn_resources)
+            .add_systems(First, load_resources.in_set(persistence::sets::Load))
+            .add_systems(First, save_resources.in_set(persistence::sets::Save))
             .add_systems(
                 FixedUpdate,
                 add_deltas_to_debit_or_credit.in_set(sets::Prepare),
@@ -142,22 +146,65 @@ impl Plugin for ResourcesPlugin {
                 FixedPostUpdate,
                 (recalculate_unlocks, recalculate_deltas).in_set(sets::Recalculate),
             )
-            .add_systems(BufferChanges, detect_resource_changes);
+            .add_systems(BufferChanges, detect_resource_changes)
+            .add_systems(
+                BufferChanges,
+                snapshot_resources.run_if(resource_exists::<PendingSnapshot>),
+            );
     }
 }
 
 fn spawn_resources(mut cmd: Commands) {
     for resource in ResourceKind::key_iter() {
-        let mut spawned = cmd.spawn((Resource(resource), Amount(0.0), Delta(0.0)));
-        if let Some(crafting_recipe_kind) = CRAFTED_RESOURCES.get(&resource) {
-            spawned.insert(Crafted(*crafting_recipe_kind));
-        }
-        if let Some(capacity) = RESOURCE_BASE_CAPACITY.get(&resource) {
-            spawned.insert(Capacity(*capacity));
-        }
+        spawn_resource(&mut cmd, resource, ResourceState::default());
     }
 }
 
+fn spawn_resource(cmd: &mut Commands, resource: ResourceKind, state: ResourceState) {
+    let mut spawned = cmd.spawn((
+        Resource(resource),
+        Amount(state.amount),
+        Delta(0.0),
+        Unlocked(state.unlocked),
+    ));
+    if let Some(crafting_recipe_kind) = CRAFTED_RESOURCES.get(&resource) {
+        spawned.insert(Crafted(*crafting_recipe_kind));
+    }
+    if let Some(capacity) = RESOURCE_BASE_CAPACITY.get(&resource) {
+        spawned.insert(Capacity(*capacity));
+    }
+}
+
+fn load_resources(
+    mut cmd: Commands,
+    load: Res<PendingLoad>,
+    resources: Query<Entity, With<Resource>>,
+) {
+    for entity in resources.iter() {
+        cmd.entity(entity).despawn();
+    }
+    for resource in ResourceKind::key_iter() {
+        let state = load.0.resources.get(&resource).copied().unwrap_or_default();
+        spawn_resource(&mut cmd, resource, state);
+    }
+}
+
+fn save_resources(
+    resources: Query<(&Resource, &Amount, &Unlocked)>,
+    mut save: ResMut<PendingSave>,
+) {
+    save.0.resources = resources
+        .iter()
+        .map(|(kind, amount, unlocked)| {
+            let state = ResourceState {
+                amount: amount.0,
+                unlocked: unlocked.0,
+            };
+            (kind.0, state)
+        })
+        .collect();
+}
+
 fn clear_debits_and_credits(mut transactions: Query<(&mut Debit, &mut Credit), With<Resource>>) {
     for (mut debit, mut credit) in transactions.iter_mut() {
         debit.0 = 0.0;
@@ -255,6 +302,18 @@ fn detect_resource_changes(
     }
 }
 
+fn snapshot_resources(
+    resources: Query<(&Resource, &Amount, &Delta, Option<&Capacity>)>,
+    mut snapshot: ResMut<PendingSnapshot>,
+) {
+    let transport = &mut snapshot.0.resources;
+    for (kind, amount, delta, capacity) in resources.iter() {
+        *transport.amounts.get_state_mut(&kind.0) = Some(amount.0);
+        *transport.deltas.get_state_mut(&kind.0) = Some(delta.0);
+        *transport.capacities.get_state_mut(&kind.0) = Some(capacity.map(|c| c.0));
+    }
+}
+
 pub mod logic {
     use super::{Amount, Capacity, Credit, Debit};
 
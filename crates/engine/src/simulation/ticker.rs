@@ -1,6 +1,6 @@
 use bevy::{
     app::{App, FixedUpdate, Plugin, Startup},
-    prelude::{Commands, Component, IntoSystemConfigs, Query, Res, Resource},
+    prelude::{Commands, Component, IntoSystemConfigs, Query, ResMut, Resource},
     time::{Fixed, Time, TimePlugin},
 };
 
@@ -27,6 +27,10 @@ impl Default for TickRate {
     }
 }
 
+/// Number of base ticks the simulation has run since the engine started.
+#[derive(Resource, Debug, Default)]
+pub struct ElapsedTicks(pub u64);
+
 impl Ticker {
     pub fn from_scale(scale: u32) -> Self {
         assert!(scale > 0, "scale should never be 0");
@@ -50,6 +54,13 @@ impl Ticker {
         self.whole = new_whole;
     }
 
+    /// Advances by `delta_ticks` base ticks at once and returns how many whole ticks passed.
+    pub fn fast_forward(&mut self, delta_ticks: f64) -> u64 {
+        let before = self.whole;
+        self.advance(delta_ticks);
+        self.whole - before
+    }
+
     pub fn just_ticked(&self) -> bool {
         self.just_ticked
     }
@@ -71,8 +82,11 @@ pub struct TickerPlugin;
 impl Plugin for TickerPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins(TimePlugin)
-            .insert_resource(Time::<Fixed>::from_hz(5.0))
+            .insert_resource(Time::<Fixed>::from_seconds(
+                TickRate::default().seconds_per_tick,
+            ))
             .insert_resource(TickRate::default())
+            .init_resource::<ElapsedTicks>()
             .add_systems(Startup, spawn)
             .add_systems(FixedUpdate, advance_simulation.in_set(sets::Main));
     }
@@ -82,12 +96,11 @@ fn spawn(mut cmd: Commands) {
     cmd.spawn(Ticker::from_scale(1));
 }
 
-fn advance_simulation(
-    time: Res<Time<Fixed>>,
-    tick_rate: Res<TickRate>,
-    mut tickers: Query<&mut Ticker>,
-) {
+fn advance_simulation(mut elapsed: ResMut<ElapsedTicks>, mut tickers: Query<&mut Ticker>) {
+    // Every fixed step is exactly one base tick, so the simulation depends only on how many steps
+    // ran and never on how long they took.
+    elapsed.0 += 1;
     for mut ticker in tickers.iter_mut() {
-        ticker.advance(time.delta().as_secs_f64() / tick_rate.seconds_per_tick);
+        ticker.advance(1.0);
     }
 }
@@ -0,0 +1,152 @@
+use std::ops::RangeInclusive;
+
+use bevy::{
+    app::{App, FixedMain, FixedUpdate, Last, Plugin, Update},
+    prelude::*,
+};
+
+use sorrow_core::{
+    communication::{EngineUpdate, TimeTransport},
+    state::time::RunningState,
+};
+
+use crate::{
+    io::{snapshot::PendingSnapshot, UpdatedEvent},
+    persistence::{self, PendingLoad, PendingSave},
+    schedules::BufferChanges,
+};
+
+/// Slowest and fastest accepted game speeds.
+pub const SPEED_RANGE: RangeInclusive<f64> = 0.1..=100.0;
+
+/// Engine-owned time controls.
+///
+/// Pausing gates the simulation sets instead of pausing virtual time, so the fixed timestep keeps
+/// ticking while paused and resuming does not replay the paused wall-clock time as a catch-up.
+///
+/// Speed scales virtual time instead of the tick rate, so every fixed step is still exactly one
+/// base tick and production per tick does not depend on the speed.
+///
+/// While paused, `pending_steps` ticks are run one per frame so the changes of each step are sent
+/// out before the next one runs.
+#[derive(Resource, Debug)]
+pub struct TimeState {
+    pub running_state: RunningState,
+    pub speed: f64,
+    pub pending_steps: u32,
+    stepping: bool,
+}
+
+impl Default for TimeState {
+    fn default() -> Self {
+        Self {
+            running_state: RunningState::default(),
+            speed: 1.0,
+            pending_steps: 0,
+            stepping: false,
+        }
+    }
+}
+
+impl TimeState {
+    pub fn is_running(&self) -> bool {
+        matches!(self.running_state, RunningState::Running)
+    }
+}
+
+/// Whether the simulation sets should run, either because time is running or a step is.
+pub fn is_running(time_state: Res<TimeState>) -> bool {
+    time_state.is_running() || time_state.stepping
+}
+
+/// Time spent with the simulation running, across all sessions.
+///
+/// Steps taken while paused are not counted, as they do not take any time to play.
+#[derive(Resource, Debug, Default)]
+pub struct PlayTime {
+    pub seconds: f64,
+}
+
+pub struct TimeControlPlugin;
+
+impl Plugin for TimeControlPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimeState>()
+            .init_resource::<PlayTime>()
+            .add_systems(First, load_play_time.in_set(persistence::sets::Load))
+            .add_systems(First, save_play_time.in_set(persistence::sets::Save))
+            .add_systems(FixedUpdate, track_play_time.run_if(is_running))
+            .add_systems(Update, run_pending_step)
+            .add_systems(Last, apply_speed.run_if(resource_changed::<TimeState>))
+            .add_systems(BufferChanges, detect_time_changes)
+            .add_systems(
+                BufferChanges,
+                snapshot_time.run_if(resource_exists::<PendingSnapshot>),
+            );
+    }
+}
+
+fn detect_time_changes(time_state: Res<TimeState>, mut updates: EventWriter<UpdatedEvent>) {
+    if time_state.is_changed() {
+        updates.send(
+            EngineUpdate::TimeChanged(TimeTransport {
+                running_state: Some(time_state.running_state),
+                speed: Some(time_state.speed),
+            })
+            .into(),
+        );
+    }
+}
+
+fn snapshot_time(time_state: Res<TimeState>, mut snapshot: ResMut<PendingSnapshot>) {
+    snapshot.0.time = TimeTransport {
+        running_state: Some(time_state.running_state),
+        speed: Some(time_state.speed),
+    };
+}
+
+fn load_play_time(load: Res<PendingLoad>, mut play_time: ResMut<PlayTime>) {
+    play_time.seconds = load.0.play_time_seconds;
+}
+
+fn save_play_time(play_time: Res<PlayTime>, mut save: ResMut<PendingSave>) {
+    save.0.play_time_seconds = play_time.seconds;
+}
+
+fn run_pending_step(world: &mut World) {
+    let mut time_state = world.resource_mut::<TimeState>();
+    if time_state.is_running() || time_state.pending_steps == 0 {
+        return;
+    }
+
+    // Stepping is not a change worth reporting, only the results of the step are.
+    let time_state = time_state.bypass_change_detection();
+    time_state.pending_steps -= 1;
+    time_state.stepping = true;
+
+    // Mirrors how the fixed main loop runs a single fixed step.
+    *world.resource_mut::<Time>() = world.resource::<Time<Fixed>>().as_generic();
+    world.run_schedule(FixedMain);
+    *world.resource_mut::<Time>() = world.resource::<Time<Virtual>>().as_generic();
+
+    world
+        .resource_mut::<TimeState>()
+        .bypass_change_detection()
+        .stepping = false;
+}
+
+fn apply_speed(time_state: Res<TimeState>, mut time: ResMut<Time<Virtual>>) {
+    time.set_relative_speed_f64(time_state.speed);
+}
+
+fn track_play_time(
+    time: Res<Time<Fixed>>,
+    time_state: Res<TimeState>,
+    mut play_time: ResMut<PlayTime>,
+) {
+    if !time_state.is_running() {
+        return;
+    }
+    // Fixed steps are in virtual time, play time is spent in wall-clock time.
+    play_time.seconds += time.delta_secs_f64() / time_state.speed;
+}
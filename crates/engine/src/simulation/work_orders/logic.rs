@@ -1,4 +1,4 @@
-use bevy::utils::{hashbrown::hash_map::Iter, HashMap};
+use bevy::utils::HashMap;
 use sorrow_core::state::resources::ResourceKind;
 
 #[derive(Debug, Default, Clone, Copy)]
@@ -83,15 +83,3 @@ impl DeltaSetStack {
         local.credit += amount;
     }
 }
-
-pub struct DeltaSetIter<'a> {
-    inner: Iter<'a, ResourceKind, ResourceDelta>,
-}
-
-impl<'a> Iterator for DeltaSetIter<'a> {
-    type Item = (&'a ResourceKind, &'a ResourceDelta);
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.inner.next()
-    }
-}
@@ -1,14 +1,15 @@
 mod logic;
 
 use bevy::{
-    app::{App, FixedUpdate, Plugin},
-    prelude::{Children, Event, EventReader, IntoSystemConfigs, Query},
+    app::{App, First, FixedUpdate, Plugin},
+    prelude::{Children, Event, EventReader, Events, IntoSystemConfigs, Query, ResMut},
 };
 
 use sorrow_core::{communication::WorkOrderKind, state::recipes::RecipeKind};
 
 use crate::{
     index::{IndexedQuery, IndexedQueryMut},
+    persistence,
     simulation::resources::{Credit, Debit},
 };
 
@@ -21,6 +22,9 @@ use super::{
 pub mod sets {
     use bevy::prelude::SystemSet;
 
+    #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+    pub struct Queue;
+
     #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
     pub struct Main;
 }
@@ -28,17 +32,45 @@ pub mod sets {
 #[derive(Event)]
 pub struct WorkOrder(pub WorkOrderKind);
 
+/// Work orders that have been accepted but not processed yet.
+///
+/// Orders received while the simulation is paused stay queued here and are processed in order on
+/// the first tick after resuming.
+#[derive(bevy::prelude::Resource, Default)]
+struct PendingWorkOrders(Vec<WorkOrderKind>);
+
 pub struct WorkOrdersPlugin;
 
 impl Plugin for WorkOrdersPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<WorkOrder>()
+            .init_resource::<PendingWorkOrders>()
+            .add_systems(First, discard_work_orders.in_set(persistence::sets::Load))
+            .add_systems(FixedUpdate, queue_work_orders.in_set(sets::Queue))
             .add_systems(FixedUpdate, process_work_orders.in_set(sets::Main));
     }
 }
 
+/// Orders placed for the game that a load replaces are dropped along with it.
+fn discard_work_orders(
+    mut work_orders: ResMut<Events<WorkOrder>>,
+    mut pending_work_orders: ResMut<PendingWorkOrders>,
+) {
+    work_orders.clear();
+    pending_work_orders.0.clear();
+}
+
+fn queue_work_orders(
+    mut work_orders: EventReader<WorkOrder>,
+    mut pending_work_orders: ResMut<PendingWorkOrders>,
+) {
+    pending_work_orders
+        .0
+        .extend(work_orders.read().map(|work_order| work_order.0));
+}
+
 fn process_work_orders(
-    mut pending_work_orders: EventReader<WorkOrder>,
+    mut pending_work_orders: ResMut<PendingWorkOrders>,
     mut resources: IndexedQueryMut<Resource, (&Amount, &mut Debit, &mut Credit, Option<&Capacity>)>,
     mut buildings: IndexedQueryMut<Building, &mut Level>,
     recipes: IndexedQuery<Recipe, &Children>,
@@ -51,11 +83,11 @@ fn process_work_orders(
         deltas.add_credit((*kind).into(), (*credit).into());
     }
 
-    for item in pending_work_orders.read() {
+    for item in pending_work_orders.0.drain(..) {
         deltas.push_new();
 
         let mut is_fulfilled: bool = true;
-        match &item.0 {
+        match &item {
             WorkOrderKind::Craft(crafting) => {
                 let ingredient_entities = recipes.item(Recipe(RecipeKind::Crafting(*crafting)));
                 let ingredients = ingredients.iter_many(ingredient_entities);
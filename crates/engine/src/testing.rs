@@ -0,0 +1,288 @@
+//! Harness for driving the full engine from tests with scripted intents.
+//!
+//! ```ignore
+//! let mut game = TestGame::new()
+//!     .intent(Intent::QueueWorkOrder(WorkOrderKind::Craft(CraftingRecipeKind::GatherCatnip)))
+//!     .run_ticks(1);
+//! assert_eq!(game.amount(ResourceKind::Catnip), 1.0);
+//! ```
+
+use std::{fmt::Debug, hash::Hash, path::Path};
+
+use bevy::ecs::query::ReadOnlyQueryData;
+
+use sorrow_core::{
+    communication::{EngineMessage, EngineUpdate, Intent, StateTable},
+    state::{
+        buildings::BuildingKind,
+        recipes::{FulfillmentState, RecipeKind},
+        resources::ResourceKind,
+        KeyIter,
+    },
+};
+
+use crate::{
+    persistence::storage::MemoryStorage,
+    runner::HeadlessRunnerPlugin,
+    simulation::{
+        buildings::{Building, Level},
+        calendar::Day,
+        fulfillment::{Fulfillment, Recipe},
+        resources::{Amount, Resource},
+    },
+    HeadlessEngine,
+};
+
+/// Set to rewrite golden files with the current output instead of comparing against them.
+const UPDATE_GOLDEN_VAR: &str = "UPDATE_GOLDEN";
+
+/// A new game on a manual clock, where every frame runs exactly one tick.
+pub struct TestGame {
+    engine: HeadlessEngine,
+    storage: MemoryStorage,
+    frame: u64,
+    messages: Vec<EngineMessage>,
+    /// Updates sent in each frame, leaving out frames without any.
+    updates: Vec<(u64, Vec<EngineUpdate>)>,
+}
+
+impl Default for TestGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TestGame {
+    pub fn new() -> Self {
+        let storage = MemoryStorage::default();
+        let mut game = Self {
+            engine: HeadlessEngine::new(
+                HeadlessRunnerPlugin::one_tick_per_update(),
+                storage.clone(),
+            ),
+            storage,
+            frame: 0,
+            messages: Vec::new(),
+            updates: Vec::new(),
+        };
+
+        // Collects what the engine sent in the frame it ran on startup.
+        game.collect();
+        game
+    }
+
+    /// Queues an intent to be handled at the start of the next tick.
+    pub fn intent(mut self, intent: Intent) -> Self {
+        self.engine.send(intent);
+        self
+    }
+
+    pub fn run_ticks(mut self, ticks: u32) -> Self {
+        for _ in 0..ticks {
+            self.update();
+        }
+        self
+    }
+
+    pub fn amount(&mut self, kind: ResourceKind) -> f64 {
+        self.find::<(&Resource, &Amount), _>(|(resource, amount)| {
+            (resource.0 == kind).then_some(amount.0)
+        })
+    }
+
+    pub fn level(&mut self, kind: BuildingKind) -> u32 {
+        self.find::<(&Building, &Level), _>(|(building, level)| {
+            (building.0 == kind).then_some(level.0)
+        })
+    }
+
+    pub fn fulfillment(&mut self, kind: RecipeKind) -> FulfillmentState {
+        self.find::<(&Recipe, &Fulfillment), _>(|(recipe, fulfillment)| {
+            (recipe.0 == kind).then_some(fulfillment.0)
+        })
+    }
+
+    pub fn day(&mut self) -> i16 {
+        self.find::<&Day, _>(|day| Some(day.0))
+    }
+
+    /// Every message sent so far, in order.
+    pub fn messages(&self) -> &[EngineMessage] {
+        &self.messages
+    }
+
+    /// Every update sent so far, in order.
+    pub fn updates(&self) -> impl Iterator<Item = &EngineUpdate> {
+        self.updates.iter().flat_map(|(_, updates)| updates)
+    }
+
+    pub fn storage(&self) -> &MemoryStorage {
+        &self.storage
+    }
+
+    /// Renders the update stream as text that stays the same between runs of the same script.
+    pub fn snapshot(&self) -> String {
+        let mut snapshot = String::new();
+        for (frame, updates) in &self.updates {
+            snapshot.push_str(&format!("# frame {frame}\n"));
+            // Updates within a frame come from systems that run in no particular order.
+            let mut lines = updates.iter().flat_map(render_update).collect::<Vec<_>>();
+            lines.sort();
+            for line in lines {
+                snapshot.push_str(&line);
+                snapshot.push('\n');
+            }
+        }
+        snapshot
+    }
+
+    /// Compares [`TestGame::snapshot`] with the golden file at `path`.
+    pub fn assert_golden(&self, path: impl AsRef<Path>) {
+        assert_golden(path, &self.snapshot());
+    }
+
+    fn update(&mut self) {
+        self.engine.update();
+        self.collect();
+    }
+
+    fn collect(&mut self) {
+        for message in self.engine.receive() {
+            match message {
+                EngineMessage::Updated(updates) if updates.is_empty() => {}
+                EngineMessage::Updated(updates) => self.updates.push((self.frame, updates)),
+                message => self.messages.push(message),
+            }
+        }
+        self.frame += 1;
+    }
+
+    fn find<D, T>(&mut self, f: impl Fn(D::Item<'_>) -> Option<T>) -> T
+    where
+        D: ReadOnlyQueryData,
+    {
+        let world = self.engine.world_mut();
+        world
+            .query::<D>()
+            .iter(world)
+            .find_map(f)
+            .expect("could not find a matching entity")
+    }
+}
+
+/// Compares `actual` with the golden file at `path`.
+///
+/// Run with `UPDATE_GOLDEN=1` to write the file instead.
+pub fn assert_golden(path: impl AsRef<Path>, actual: &str) {
+    let path = path.as_ref();
+
+    if std::env::var_os(UPDATE_GOLDEN_VAR).is_some() {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).expect("could not create golden directory");
+        }
+        std::fs::write(path, actual).expect("could not write golden file");
+        return;
+    }
+
+    let expected = std::fs::read_to_string(path).unwrap_or_else(|error| {
+        panic!(
+            "could not read golden file {}: {error}; run with {UPDATE_GOLDEN_VAR}=1 to create it",
+            path.display()
+        )
+    });
+    if expected != actual {
+        panic!(
+            "output does not match {}; run with {UPDATE_GOLDEN_VAR}=1 to accept it\n\n{}",
+            path.display(),
+            diff(&expected, actual)
+        );
+    }
+}
+
+fn render_update(update: &EngineUpdate) -> Vec<String> {
+    let mut lines = Vec::new();
+    match update {
+        EngineUpdate::CalendarChanged(calendar) => {
+            push_field(&mut lines, "calendar.day", &calendar.day);
+            push_field(&mut lines, "calendar.season", &calendar.season);
+            push_field(&mut lines, "calendar.year", &calendar.year);
+        }
+        EngineUpdate::BuildingsChanged(buildings) => {
+            push_table(&mut lines, "buildings.level", &buildings.levels);
+        }
+        EngineUpdate::FulfillmentsChanged(fulfillments) => {
+            push_table(
+                &mut lines,
+                "fulfillments.fulfillment",
+                &fulfillments.fulfillments,
+            );
+            push_table(
+                &mut lines,
+                "fulfillments.required_amount",
+                &fulfillments.required_amounts,
+            );
+        }
+        EngineUpdate::ResourcesChanged(resources) => {
+            push_table(&mut lines, "resources.amount", &resources.amounts);
+            push_table(&mut lines, "resources.delta", &resources.deltas);
+            push_table(&mut lines, "resources.capacity", &resources.capacities);
+        }
+        EngineUpdate::PacingChanged(pacing) => {
+            push_field(&mut lines, "pacing.catching_up", &pacing.catching_up);
+            push_field(&mut lines, "pacing.behind_seconds", &pacing.behind_seconds);
+        }
+        EngineUpdate::TimeChanged(time) => {
+            push_field(&mut lines, "time.running_state", &time.running_state);
+            push_field(&mut lines, "time.speed", &time.speed);
+        }
+        EngineUpdate::VisibilityChanged(visibility) => {
+            push_table(&mut lines, "visibility.node", &visibility.nodes);
+        }
+    }
+    lines
+}
+
+fn push_field<V: Debug>(lines: &mut Vec<String>, name: &str, value: &Option<V>) {
+    if let Some(value) = value {
+        lines.push(format!("{name} = {value:?}"));
+    }
+}
+
+fn push_table<K, V>(lines: &mut Vec<String>, name: &str, table: &StateTable<K, V>)
+where
+    K: Eq + Hash + Debug + KeyIter<Item = K>,
+    V: Debug,
+{
+    // Tables are hash maps, so they are sorted to keep the output stable.
+    let mut entries = table
+        .iter()
+        .filter_map(|(key, value)| {
+            value
+                .as_ref()
+                .map(|value| format!("{name} {key:?} = {value:?}"))
+        })
+        .collect::<Vec<_>>();
+    entries.sort();
+    lines.extend(entries);
+}
+
+fn diff(expected: &str, actual: &str) -> String {
+    let expected = expected.lines().collect::<Vec<_>>();
+    let actual = actual.lines().collect::<Vec<_>>();
+    let mut diff = String::new();
+    for line in 0..expected.len().max(actual.len()) {
+        match (expected.get(line), actual.get(line)) {
+            (Some(e), Some(a)) if e == a => {}
+            (e, a) => {
+                diff.push_str(&format!("line {}:\n", line + 1));
+                if let Some(e) = e {
+                    diff.push_str(&format!("  - {e}\n"));
+                }
+                if let Some(a) = a {
+                    diff.push_str(&format!("  + {a}\n"));
+                }
+            }
+        }
+    }
+    diff
+}
@@ -1,16 +1,23 @@
+use std::collections::BTreeSet;
+
 use bevy::{
-    app::{Plugin, Startup},
-    prelude::{Changed, Commands, Component, EventWriter, Query},
+    app::{First, Plugin, Startup},
+    prelude::{
+        resource_exists, Changed, Commands, Component, Entity, EventWriter, IntoSystemConfigs,
+        Query, Res, ResMut, With,
+    },
 };
 
 use sorrow_core::{
     communication::{EngineUpdate, VisibilityTransport},
-    state::ui::{NodeId, NODE_VISIBILITY},
+    persistence::SaveState,
+    state::{ui::NodeId, KeyIter},
 };
 
 use crate::{
     index::{IndexedQueryMut, LookupIndexPlugin},
-    io::UpdatedEvent,
+    io::{snapshot::PendingSnapshot, UpdatedEvent},
+    persistence::{self, PendingLoad, PendingSave},
     schedules::{BufferChanges, Recalculate},
     simulation::{fulfillment::Recipe, resources::Resource, Unlocked},
 };
@@ -30,22 +37,49 @@ impl Plugin for VisibilityPlugin {
     fn build(&self, app: &mut bevy::prelude::App) {
         app.add_plugins(LookupIndexPlugin::<Node>::new())
             .add_systems(Startup, spawn_ui_nodes)
+            .add_systems(First, load_ui_nodes.in_set(persistence::sets::Load))
+            .add_systems(First, save_ui_nodes.in_set(persistence::sets::Save))
             .add_systems(Recalculate, recalculate_visibility)
-            .add_systems(BufferChanges, detect_visibility_changes);
+            .add_systems(BufferChanges, detect_visibility_changes)
+            .add_systems(
+                BufferChanges,
+                snapshot_visibility.run_if(resource_exists::<PendingSnapshot>),
+            );
     }
 }
 
 fn spawn_ui_nodes(mut cmd: Commands) {
-    cmd.spawn_batch(NODE_VISIBILITY.iter().map(|(id, is_visible)| {
-        (
-            Node(*id),
-            if *is_visible {
-                Visibility::Visible
-            } else {
-                Visibility::Invisible
-            },
-        )
-    }));
+    let visible_nodes = SaveState::default().visible_nodes;
+    cmd.spawn_batch(node_bundles(&visible_nodes));
+}
+
+fn load_ui_nodes(mut cmd: Commands, load: Res<PendingLoad>, nodes: Query<Entity, With<Node>>) {
+    for entity in nodes.iter() {
+        cmd.entity(entity).despawn();
+    }
+    cmd.spawn_batch(node_bundles(&load.0.visible_nodes));
+}
+
+fn node_bundles(visible_nodes: &BTreeSet<NodeId>) -> Vec<(Node, Visibility)> {
+    NodeId::key_iter()
+        .map(|id| {
+            (
+                Node(id),
+                if visible_nodes.contains(&id) {
+                    Visibility::Visible
+                } else {
+                    Visibility::Invisible
+                },
+            )
+        })
+        .collect()
+}
+
+fn save_ui_nodes(nodes: Query<(&Node, &Visibility)>, mut save: ResMut<PendingSave>) {
+    save.0.visible_nodes = nodes
+        .iter()
+        .filter_map(|(node, visibility)| (*visibility == Visibility::Visible).then_some(node.0))
+        .collect();
 }
 
 fn recalculate_visibility(
@@ -95,3 +129,10 @@ fn detect_visibility_changes(
         updates.send(EngineUpdate::VisibilityChanged(transport).into());
     }
 }
+
+fn snapshot_visibility(query: Query<(&Node, &Visibility)>, mut snapshot: ResMut<PendingSnapshot>) {
+    let transport = &mut snapshot.0.visibility;
+    for (node, visibility) in query.iter() {
+        *transport.nodes.get_state_mut(&node.0) = Some(matches!(visibility, Visibility::Visible));
+    }
+}
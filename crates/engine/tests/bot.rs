@@ -0,0 +1,10 @@
+use sorrow_engine::{bot, testing::assert_golden};
+
+#[test]
+fn bot_report_matches_golden() {
+    let report = bot::run(20_000);
+    assert_golden(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden/bot_report.txt"),
+        &format!("{report}\n"),
+    );
+}
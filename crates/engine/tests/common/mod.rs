@@ -0,0 +1,100 @@
+//! Helpers shared by the integration tests, each of which only uses some of them.
+#![allow(dead_code)]
+
+use std::{
+    cell::{Cell, RefCell},
+    rc::Rc,
+};
+
+use sorrow_core::{
+    communication::{Intent, WorkOrderKind},
+    state::{buildings::BuildingKind, recipes::CraftingRecipeKind},
+};
+use sorrow_engine::{
+    storage::{MemoryStorage, SaveStorage, StorageEvent},
+    testing::TestGame,
+};
+
+pub const GATHER_CATNIP: Intent =
+    Intent::QueueWorkOrder(WorkOrderKind::Craft(CraftingRecipeKind::GatherCatnip));
+pub const BUILD_FIELD: Intent =
+    Intent::QueueWorkOrder(WorkOrderKind::Construct(BuildingKind::CatnipField));
+
+/// Gathers catnip `times` times within a single tick.
+pub fn gather_catnip(mut game: TestGame, times: usize) -> TestGame {
+    for _ in 0..times {
+        game = game.intent(GATHER_CATNIP);
+    }
+    game.run_ticks(1)
+}
+
+/// Storage that holds back the results of its requests while it is closed, like a slow backend.
+///
+/// Requests are carried out immediately, so the saves can be inspected through [`Self::memory`],
+/// unless writes lag.
+#[derive(Clone, Default)]
+pub struct GatedStorage {
+    pub memory: MemoryStorage,
+    closed: Rc<Cell<bool>>,
+    held: Rc<RefCell<Vec<StorageEvent>>>,
+    lagging: Rc<Cell<bool>>,
+    unwritten: Rc<RefCell<Vec<(String, String)>>>,
+}
+
+impl GatedStorage {
+    pub fn closed(memory: MemoryStorage) -> Self {
+        let storage = Self {
+            memory,
+            ..Default::default()
+        };
+        storage.closed.set(true);
+        storage
+    }
+
+    pub fn open(&self) {
+        self.closed.set(false);
+    }
+
+    pub fn close(&self) {
+        self.closed.set(true);
+    }
+
+    /// Makes writes land only once the storage is open, so that reads made while it is closed
+    /// miss them.
+    pub fn lag_writes(&self) {
+        self.lagging.set(true);
+    }
+}
+
+impl SaveStorage for GatedStorage {
+    fn read(&mut self, key: &str) {
+        self.memory.read(key);
+    }
+
+    fn write(&mut self, key: &str, data: String) {
+        if self.lagging.get() && self.closed.get() {
+            self.unwritten.borrow_mut().push((key.to_string(), data));
+        } else {
+            self.memory.write(key, data);
+        }
+    }
+
+    fn delete(&mut self, key: &str) {
+        self.memory.delete(key);
+    }
+
+    fn poll(&mut self) -> Vec<StorageEvent> {
+        if !self.closed.get() {
+            for (key, data) in self.unwritten.take() {
+                self.memory.write(&key, data);
+            }
+        }
+        let mut held = self.held.borrow_mut();
+        held.extend(self.memory.poll());
+        if self.closed.get() {
+            Vec::new()
+        } else {
+            std::mem::take(&mut *held)
+        }
+    }
+}
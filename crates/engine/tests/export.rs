@@ -0,0 +1,69 @@
+use base64::{engine::general_purpose::STANDARD, Engine};
+
+use sorrow_core::persistence::{LoadError, SavePayload, SaveState};
+use sorrow_engine::export;
+
+fn exported() -> (SavePayload, String) {
+    let mut state = SaveState::default();
+    state.calendar.year = 7;
+    let payload = SavePayload::encode(&state);
+    let text = export::encode(&payload);
+    (payload, text)
+}
+
+fn is_corrupted(result: Result<SavePayload, LoadError>) -> bool {
+    matches!(result, Err(LoadError::Corrupted(_)))
+}
+
+#[test]
+fn round_trips_a_payload() {
+    let (payload, text) = exported();
+
+    assert_eq!(export::decode(&text), Ok(payload));
+}
+
+#[test]
+fn ignores_whitespace_from_wrapping() {
+    let (payload, text) = exported();
+    let wrapped = text
+        .as_bytes()
+        .chunks(20)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n  ");
+
+    assert_eq!(export::decode(&wrapped), Ok(payload));
+}
+
+#[test]
+fn refuses_text_that_is_not_base64() {
+    assert!(is_corrupted(export::decode("not base64!")));
+}
+
+#[test]
+fn refuses_truncated_text() {
+    let (_, text) = exported();
+
+    assert!(is_corrupted(export::decode(&text[..text.len() / 2])));
+    assert!(is_corrupted(export::decode(&STANDARD.encode([0, 1]))));
+}
+
+#[test]
+fn refuses_text_with_a_wrong_checksum() {
+    let (_, text) = exported();
+    let mut bytes = STANDARD.decode(text).unwrap();
+    bytes[0] ^= 0xff;
+
+    assert_eq!(
+        export::decode(&STANDARD.encode(bytes)),
+        Err(LoadError::Corrupted("checksum does not match".to_string()))
+    );
+}
+
+#[test]
+fn refuses_text_that_does_not_inflate() {
+    let mut bytes = crc32fast::hash(b"").to_be_bytes().to_vec();
+    bytes.extend_from_slice(&[0xff; 16]);
+
+    assert!(is_corrupted(export::decode(&STANDARD.encode(bytes))));
+}
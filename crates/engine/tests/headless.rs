@@ -0,0 +1,242 @@
+use std::time::Duration;
+
+use sorrow_core::{
+    communication::{EngineMessage, EngineUpdate, Intent, ProtocolInfo, WorkOrderKind},
+    diagnostics::DiagnosticBundle,
+    persistence::{dump, SavePayload, SaveState},
+    state::{
+        buildings::BuildingKind, recipes::CraftingRecipeKind, resources::ResourceKind, ui::NodeId,
+        KeyIter,
+    },
+    utils::unix_millis,
+};
+use sorrow_engine::{export, storage::MemoryStorage, HeadlessEngine, HeadlessRunnerPlugin};
+
+const STEP: Duration = Duration::from_millis(200);
+
+fn calendar_days(messages: &[EngineMessage]) -> Vec<i16> {
+    messages
+        .iter()
+        .filter_map(|message| match message {
+            EngineMessage::Updated(updates) => Some(updates),
+            _ => None,
+        })
+        .flatten()
+        .filter_map(|update| match update {
+            EngineUpdate::CalendarChanged(calendar) => calendar.day,
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn runs_the_simulation_on_a_manual_clock() {
+    let mut engine =
+        HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), MemoryStorage::default());
+
+    // A day lasts 10 ticks, one tick per step.
+    for _ in 0..24 {
+        engine.update();
+    }
+
+    let messages = engine.receive();
+    assert!(messages
+        .iter()
+        .any(|m| matches!(m, EngineMessage::Slots(_))));
+    assert_eq!(calendar_days(&messages).last(), Some(&2));
+}
+
+#[test]
+fn writes_the_active_slot_on_shutdown() {
+    let storage = MemoryStorage::default();
+    let mut engine = HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), storage.clone());
+
+    engine.send(Intent::CreateSlot("Test".to_string()));
+    for _ in 0..5 {
+        engine.update();
+    }
+    assert!(engine
+        .receive()
+        .iter()
+        .any(|m| matches!(m, EngineMessage::SlotOpened(slot) if slot.name == "Test")));
+
+    engine.shutdown();
+    assert!(storage.get("slot/0").is_some());
+    assert!(storage.get("slots").is_some());
+}
+
+#[test]
+fn diagnostics_bundle_the_state_and_recent_activity() {
+    let mut engine =
+        HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), MemoryStorage::default());
+    engine.send(Intent::QueueWorkOrder(WorkOrderKind::Craft(
+        CraftingRecipeKind::GatherCatnip,
+    )));
+    engine.update();
+    engine.send(Intent::ExportDiagnostics);
+    engine.update();
+
+    let text = engine
+        .receive()
+        .into_iter()
+        .find_map(|message| match message {
+            EngineMessage::Diagnostics(text) => Some(text),
+            _ => None,
+        })
+        .expect("diagnostics should be sent within the frame");
+    let bundle: DiagnosticBundle =
+        serde_json::from_str(&export::unpack(&text).expect("the bundle should unpack"))
+            .expect("the bundle should deserialize");
+
+    let state = bundle.state.decode().expect("the state should decode");
+    assert_eq!(state.resources[&ResourceKind::Catnip].amount, 1.0);
+    assert_eq!(bundle.intents.len(), 2);
+    assert!(bundle
+        .messages
+        .iter()
+        .any(|m| m.contains("ResourcesChanged")));
+}
+
+#[test]
+fn loads_an_edited_state_dump() {
+    let mut engine =
+        HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), MemoryStorage::default());
+    engine.send(Intent::Save);
+    engine.update();
+    let mut state = engine
+        .receive()
+        .into_iter()
+        .find_map(|message| match message {
+            EngineMessage::Saved(payload) => Some(payload),
+            _ => None,
+        })
+        .expect("the save should be sent within the frame")
+        .decode()
+        .expect("the save should decode");
+
+    state.saved_at = None;
+    dump::set(&mut state, "resource.wood", "150").expect("wood should be settable");
+    dump::set(&mut state, "building.catnip_field", "12").expect("fields should be settable");
+    engine.send(Intent::Load(SavePayload::encode_dump(&state)));
+    engine.send(Intent::Save);
+    engine.update();
+
+    let loaded = engine
+        .receive()
+        .into_iter()
+        .find_map(|message| match message {
+            EngineMessage::Saved(payload) => Some(payload),
+            _ => None,
+        })
+        .expect("the save should be sent within the frame")
+        .decode()
+        .expect("the save should decode");
+    let fields = dump::fields(&loaded);
+    assert_eq!(fields["resource.wood"], "150");
+    assert_eq!(fields["building.catnip_field"], "12");
+}
+
+#[test]
+fn sends_a_snapshot_of_everything_on_request() {
+    let mut engine =
+        HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), MemoryStorage::default());
+    for _ in 0..3 {
+        engine.update();
+    }
+    engine.receive();
+
+    engine.send(Intent::Snapshot);
+    engine.update();
+    let snapshot = engine
+        .receive()
+        .into_iter()
+        .find_map(|message| match message {
+            EngineMessage::Snapshot(snapshot) => Some(snapshot),
+            _ => None,
+        })
+        .expect("the snapshot should be sent within the frame");
+
+    // Values that have not changed since startup are part of it all the same.
+    assert!(ResourceKind::key_iter().all(|kind| {
+        snapshot.resources.amounts.get_state(&kind).is_some()
+            && snapshot.resources.capacities.get_state(&kind).is_some()
+    }));
+    assert!(BuildingKind::key_iter().all(|kind| snapshot
+        .buildings
+        .levels
+        .get_state(&kind)
+        .is_some()));
+    assert!(NodeId::key_iter().all(|node| snapshot.visibility.nodes.get_state(&node).is_some()));
+    assert_eq!(snapshot.calendar.year, Some(0));
+    assert_eq!(snapshot.time.speed, Some(1.0));
+}
+
+#[test]
+fn answers_a_handshake_with_compatibility() {
+    let mut engine =
+        HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), MemoryStorage::default());
+    engine.update();
+    engine.receive();
+
+    let mut handshake = |client: ProtocolInfo| {
+        engine.send(Intent::Handshake(client));
+        engine.update();
+        engine
+            .receive()
+            .into_iter()
+            .find_map(|message| match message {
+                EngineMessage::Handshake(reply) => Some(reply),
+                _ => None,
+            })
+            .expect("the handshake should be answered within the frame")
+    };
+
+    let reply = handshake(ProtocolInfo::current());
+    assert!(reply.compatible);
+    assert_eq!(reply.engine, ProtocolInfo::current());
+
+    // A page cached from another build hashes other sources.
+    let stale = ProtocolInfo {
+        schema_hash: ProtocolInfo::current().schema_hash ^ 1,
+        ..ProtocolInfo::current()
+    };
+    assert!(!handshake(stale).compatible);
+}
+
+#[test]
+fn catches_up_on_the_time_since_a_save() {
+    let mut engine =
+        HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), MemoryStorage::default());
+
+    let mut state = SaveState::default();
+    state
+        .buildings
+        .entry(BuildingKind::CatnipField)
+        .or_default()
+        .level = 5;
+    state
+        .resources
+        .entry(ResourceKind::Catnip)
+        .or_default()
+        .unlocked = true;
+    state.saved_at = Some(unix_millis() - 60 * 60 * 1000);
+    engine.send(Intent::Load(SavePayload::encode(&state)));
+
+    let summary = (0..50)
+        .find_map(|_| {
+            engine.update();
+            engine
+                .receive()
+                .into_iter()
+                .find_map(|message| match message {
+                    EngineMessage::OfflineProgress(summary) => Some(summary),
+                    _ => None,
+                })
+        })
+        .expect("the hour away should be caught up on");
+
+    assert!((3600.0..3601.0).contains(&summary.elapsed_seconds));
+    // An hour is 18 000 base ticks, and a day is 10 of them.
+    assert!((1799..=1800).contains(&summary.days));
+    assert!(summary.resources[&ResourceKind::Catnip] > 0.0);
+}
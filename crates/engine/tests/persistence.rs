@@ -0,0 +1,216 @@
+mod common;
+
+use std::time::Duration;
+
+use sorrow_core::{
+    communication::{EngineMessage, Intent},
+    persistence::{SavePayload, SaveState, SlotId, SlotMetadata},
+};
+use sorrow_engine::{storage::MemoryStorage, HeadlessEngine, HeadlessRunnerPlugin};
+
+use common::GatedStorage;
+
+/// A single tick per frame.
+const STEP: Duration = Duration::from_millis(200);
+
+/// Ticks between autosaves, i.e. 30 seconds at the default tick rate.
+const AUTOSAVE_INTERVAL: usize = 150;
+
+fn stored_state(storage: &MemoryStorage, key: &str) -> SaveState {
+    SavePayload(storage.get(key).expect("the slot should be stored"))
+        .decode()
+        .expect("the stored save should decode")
+}
+
+fn run(engine: &mut HeadlessEngine, frames: usize) -> Vec<EngineMessage> {
+    for _ in 0..frames {
+        engine.update();
+    }
+    engine.receive()
+}
+
+/// The slots as last sent in the messages.
+fn listed_slots(messages: &[EngineMessage]) -> Vec<SlotMetadata> {
+    messages
+        .iter()
+        .rev()
+        .find_map(|message| match message {
+            EngineMessage::Slots(slots) => Some(slots.clone()),
+            _ => None,
+        })
+        .expect("the slots should be listed")
+}
+
+fn create_named_slot(engine: &mut HeadlessEngine, name: &str) -> SlotMetadata {
+    engine.send(Intent::CreateSlot(name.to_string()));
+    run(engine, 3)
+        .into_iter()
+        .find_map(|message| match message {
+            EngineMessage::SlotOpened(slot) => Some(slot),
+            _ => None,
+        })
+        .expect("the slot should be opened")
+}
+
+fn create_slot(engine: &mut HeadlessEngine) {
+    create_named_slot(engine, "Test");
+}
+
+#[test]
+fn autosaves_the_active_slot_every_interval() {
+    let storage = MemoryStorage::default();
+    let mut engine = HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), storage.clone());
+    create_slot(&mut engine);
+    let created = stored_state(&storage, "slot/0").play_time_seconds;
+
+    for _ in 0..AUTOSAVE_INTERVAL - 10 {
+        engine.update();
+    }
+    assert_eq!(stored_state(&storage, "slot/0").play_time_seconds, created);
+
+    for _ in 0..20 {
+        engine.update();
+    }
+    let autosaved = stored_state(&storage, "slot/0").play_time_seconds;
+    assert!(
+        autosaved > created + 25.0,
+        "expected an autosave after 30 seconds, got {autosaved} seconds of play"
+    );
+}
+
+#[test]
+fn shutdown_waits_for_its_own_write() {
+    let storage = GatedStorage::default();
+    let mut engine = HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), storage.clone());
+    create_slot(&mut engine);
+
+    // The writes of creating the slot are still in progress when the flush is written.
+    storage.close();
+    engine.send(Intent::Save);
+    engine.update();
+    assert!(!engine.shutdown());
+
+    storage.open();
+    assert!(engine.shutdown());
+}
+
+#[test]
+fn does_not_write_before_the_stored_slots_are_read() {
+    let memory = MemoryStorage::default();
+    {
+        let mut engine = HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), memory.clone());
+        create_slot(&mut engine);
+        engine.shutdown();
+    }
+    let index = memory.get("slots");
+    let slot = memory.get("slot/0");
+
+    let storage = GatedStorage::closed(memory.clone());
+    let mut engine = HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), storage.clone());
+    engine.send(Intent::CreateSlot("Other".to_string()));
+    run(&mut engine, AUTOSAVE_INTERVAL + 10);
+    engine.shutdown();
+
+    assert_eq!(memory.get("slots"), index);
+    assert_eq!(memory.get("slot/0"), slot);
+}
+
+#[test]
+fn creates_renames_and_deletes_slots() {
+    let storage = MemoryStorage::default();
+    let mut engine = HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), storage.clone());
+
+    let first = create_named_slot(&mut engine, "First");
+    let second = create_named_slot(&mut engine, "Second");
+    assert_eq!((first.id, second.id), (SlotId(0), SlotId(1)));
+    assert!(storage.get("slot/0").is_some());
+    assert!(storage.get("slot/1").is_some());
+
+    engine.send(Intent::RenameSlot(first.id, "Renamed".to_string()));
+    let slots = listed_slots(&run(&mut engine, 1));
+    let names: Vec<_> = slots.iter().map(|slot| slot.name.as_str()).collect();
+    assert_eq!(names, ["Renamed", "Second"]);
+
+    engine.send(Intent::DeleteSlot(first.id));
+    let slots = listed_slots(&run(&mut engine, 1));
+    assert_eq!(
+        slots.iter().map(|slot| slot.id).collect::<Vec<_>>(),
+        [second.id]
+    );
+    assert_eq!(storage.get("slot/0"), None);
+
+    // The index is stored along with the slots.
+    let stored: Vec<SlotMetadata> =
+        serde_json::from_str(&storage.get("slots").expect("the index should be stored")).unwrap();
+    assert_eq!(stored, slots);
+}
+
+#[test]
+fn does_not_delete_the_open_slot() {
+    let storage = MemoryStorage::default();
+    let mut engine = HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), storage.clone());
+    let slot = create_named_slot(&mut engine, "Open");
+    let created = stored_state(&storage, "slot/0").play_time_seconds;
+
+    engine.send(Intent::DeleteSlot(slot.id));
+    let slots = listed_slots(&run(&mut engine, 1));
+    assert_eq!(
+        slots.iter().map(|slot| slot.id).collect::<Vec<_>>(),
+        [slot.id]
+    );
+
+    // Autosaves still have somewhere to go.
+    run(&mut engine, AUTOSAVE_INTERVAL + 10);
+    assert!(stored_state(&storage, "slot/0").play_time_seconds > created);
+}
+
+#[test]
+fn duplicates_the_latest_save_of_the_open_slot() {
+    let storage = GatedStorage::default();
+    let mut engine = HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), storage.clone());
+    let slot = create_named_slot(&mut engine, "Original");
+    run(&mut engine, 20);
+
+    // The open slot is saved before it is copied, and the copy waits for that write.
+    storage.lag_writes();
+    storage.close();
+    engine.send(Intent::DuplicateSlot(slot.id, "Copy".to_string()));
+    let slots = listed_slots(&run(&mut engine, 5));
+    let copy = &slots[1];
+    assert_eq!((copy.id, copy.name.as_str()), (SlotId(1), "Copy"));
+    assert_eq!(copy.play_time_seconds, slots[0].play_time_seconds);
+    assert_eq!(storage.memory.get("slot/1"), None);
+
+    storage.open();
+    run(&mut engine, 5);
+
+    let original = stored_state(&storage.memory, "slot/0");
+    assert!(original.play_time_seconds > 0.0);
+    assert_eq!(stored_state(&storage.memory, "slot/1"), original);
+}
+
+#[test]
+fn numbers_slots_created_early_after_the_stored_ones() {
+    let memory = MemoryStorage::default();
+    {
+        let mut engine = HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), memory.clone());
+        create_slot(&mut engine);
+        engine.shutdown();
+    }
+    let stored = memory.get("slot/0");
+
+    let storage = GatedStorage::closed(memory.clone());
+    let mut engine = HeadlessEngine::new(HeadlessRunnerPlugin::manual(STEP), storage.clone());
+    engine.send(Intent::CreateSlot("Early".to_string()));
+    run(&mut engine, 3);
+
+    storage.open();
+    let messages = run(&mut engine, 3);
+    let opened = messages.iter().find_map(|message| match message {
+        EngineMessage::SlotOpened(slot) => Some(slot),
+        _ => None,
+    });
+    assert_eq!(opened.map(|slot| slot.id), Some(SlotId(1)));
+    assert_eq!(listed_slots(&messages).len(), 2);
+    assert_eq!(memory.get("slot/0"), stored);
+}
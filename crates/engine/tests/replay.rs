@@ -0,0 +1,106 @@
+mod common;
+
+use std::time::Duration;
+
+use sorrow_core::{
+    communication::{EngineMessage, Intent, TimeControl},
+    persistence::{Replay, SavePayload, SaveState},
+};
+use sorrow_engine::{play_back, storage::MemoryStorage, HeadlessEngine, HeadlessRunnerPlugin};
+
+use common::{BUILD_FIELD, GATHER_CATNIP};
+
+/// Plays a session where frames do not line up with ticks, so some frames run none and some run
+/// several, and returns its replay.
+fn record(setup: impl FnOnce(&mut HeadlessEngine)) -> Replay {
+    let mut engine = HeadlessEngine::new(
+        HeadlessRunnerPlugin::manual(Duration::from_millis(130)),
+        MemoryStorage::default(),
+    );
+    setup(&mut engine);
+
+    for frame in 0..400 {
+        match frame {
+            50 => engine.send(Intent::TimeControl(TimeControl::SetSpeed(3.0))),
+            120 => engine.send(Intent::TimeControl(TimeControl::Pause)),
+            125 => engine.send(Intent::TimeControl(TimeControl::Step(4))),
+            140 => engine.send(Intent::TimeControl(TimeControl::Start)),
+            _ => {}
+        }
+        if frame % 3 == 0 {
+            engine.send(GATHER_CATNIP);
+        }
+        if frame % 40 == 0 {
+            engine.send(BUILD_FIELD);
+        }
+        engine.update();
+    }
+
+    engine.send(Intent::ExportReplay);
+    engine.update();
+    engine
+        .receive()
+        .into_iter()
+        .find_map(|message| match message {
+            EngineMessage::Replay(replay) => Some(replay),
+            _ => None,
+        })
+        .expect("the replay should be sent within the frame")
+}
+
+#[test]
+fn playback_reproduces_a_new_game() {
+    let replay = record(|_| {});
+    assert!(replay.initial.is_none());
+    assert!(replay.ticks > 0);
+
+    let outcome = play_back(&replay).expect("the replay should play back");
+    assert!(outcome.matches);
+}
+
+#[test]
+fn playback_starts_from_the_last_load() {
+    let state = SaveState {
+        play_time_seconds: 60.0,
+        ..SaveState::default()
+    };
+    let replay = record(|engine| {
+        engine.send(GATHER_CATNIP);
+        engine.update();
+        engine.send(Intent::Load(SavePayload::encode(&state)));
+        engine.update();
+    });
+    assert!(replay.initial.is_some());
+
+    let outcome = play_back(&replay).expect("the replay should play back");
+    assert!(outcome.matches);
+}
+
+#[test]
+fn playback_notices_a_different_outcome() {
+    let mut replay = record(|_| {});
+    let mut outcome = replay.outcome.decode().expect("the outcome should decode");
+    outcome.play_time_seconds += 1.0;
+    replay.outcome = SavePayload::encode(&outcome);
+
+    let outcome = play_back(&replay).expect("the replay should play back");
+    assert!(!outcome.matches);
+}
+
+#[test]
+fn playback_reproduces_a_recording_that_started_over() {
+    let replay = record(|engine| {
+        // More inputs than a recording holds, spread over a few hundred ticks.
+        for _ in 0..300 {
+            for _ in 0..35 {
+                engine.send(GATHER_CATNIP);
+            }
+            engine.update();
+        }
+    });
+    assert!(replay.initial.is_some());
+    assert!(replay.entries.len() < 10_000);
+
+    let outcome = play_back(&replay).expect("the replay should play back");
+    assert!(outcome.matches);
+}
@@ -0,0 +1,46 @@
+mod common;
+
+use sorrow_core::{
+    communication::Intent,
+    state::{buildings::BuildingKind, resources::ResourceKind},
+};
+use sorrow_engine::testing::TestGame;
+
+use common::{gather_catnip, BUILD_FIELD};
+
+/// Base ticks in an in-game day.
+const TICKS_PER_DAY: u32 = 10;
+
+#[test]
+fn rewinding_undoes_a_construction() {
+    let game = gather_catnip(TestGame::new(), 10).run_ticks(TICKS_PER_DAY);
+    let mut game = game.intent(BUILD_FIELD).run_ticks(1);
+    assert_eq!(game.level(BuildingKind::CatnipField), 1);
+
+    let mut game = game.intent(Intent::Rewind { days: 1 }).run_ticks(1);
+    assert_eq!(game.level(BuildingKind::CatnipField), 0);
+    assert_eq!(game.amount(ResourceKind::Catnip), 10.0);
+    assert_eq!(game.day(), 1);
+}
+
+#[test]
+fn rewinding_goes_back_whole_days() {
+    let mut game = TestGame::new().run_ticks(5 * TICKS_PER_DAY + 5);
+    assert_eq!(game.day(), 5);
+
+    let mut game = game.intent(Intent::Rewind { days: 3 }).run_ticks(1);
+    assert_eq!(game.day(), 3);
+
+    // The restored day is kept, so rewinding again lands on the day before it.
+    let mut game = game.intent(Intent::Rewind { days: 2 }).run_ticks(1);
+    assert_eq!(game.day(), 2);
+}
+
+#[test]
+fn rewinding_past_the_oldest_snapshot_stops_there() {
+    let mut game = TestGame::new()
+        .run_ticks(2 * TICKS_PER_DAY)
+        .intent(Intent::Rewind { days: 50 })
+        .run_ticks(1);
+    assert_eq!(game.day(), 0);
+}
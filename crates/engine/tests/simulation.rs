@@ -0,0 +1,142 @@
+mod common;
+
+use sorrow_core::{
+    communication::{EngineMessage, EngineUpdate, Intent, TimeControl},
+    state::{
+        buildings::BuildingKind,
+        recipes::{FulfillmentState, RecipeKind},
+        resources::ResourceKind,
+    },
+};
+use sorrow_engine::testing::TestGame;
+
+use common::{gather_catnip, BUILD_FIELD, GATHER_CATNIP};
+
+const FIELD_RECIPE: RecipeKind = RecipeKind::Building(BuildingKind::CatnipField);
+
+#[test]
+fn gathering_catnip_adds_one_per_order() {
+    let mut game = gather_catnip(TestGame::new(), 3);
+    assert_eq!(game.amount(ResourceKind::Catnip), 3.0);
+}
+
+#[test]
+fn building_a_field_spends_its_price() {
+    let mut game = gather_catnip(TestGame::new(), 10);
+    assert_eq!(game.fulfillment(FIELD_RECIPE), FulfillmentState::Fulfilled);
+
+    let mut game = game.intent(BUILD_FIELD).run_ticks(1);
+    assert_eq!(game.level(BuildingKind::CatnipField), 1);
+    assert!(game.amount(ResourceKind::Catnip) < 10.0);
+    assert_eq!(
+        game.fulfillment(FIELD_RECIPE),
+        FulfillmentState::Unfulfilled
+    );
+}
+
+#[test]
+fn fields_produce_catnip_over_time() {
+    let mut game = gather_catnip(TestGame::new(), 10)
+        .intent(BUILD_FIELD)
+        .run_ticks(1);
+    let before = game.amount(ResourceKind::Catnip);
+
+    let mut game = game.run_ticks(10);
+    assert!(game.amount(ResourceKind::Catnip) > before);
+}
+
+#[test]
+fn work_orders_wait_while_paused() {
+    let mut game = TestGame::new()
+        .intent(Intent::TimeControl(TimeControl::Pause))
+        .run_ticks(1)
+        .intent(GATHER_CATNIP)
+        .run_ticks(5);
+    assert_eq!(game.amount(ResourceKind::Catnip), 0.0);
+
+    let mut game = game
+        .intent(Intent::TimeControl(TimeControl::Start))
+        .run_ticks(1);
+    assert_eq!(game.amount(ResourceKind::Catnip), 1.0);
+}
+
+fn build_a_field_and_pause() -> TestGame {
+    gather_catnip(TestGame::new(), 10)
+        .intent(BUILD_FIELD)
+        .run_ticks(1)
+        .intent(Intent::TimeControl(TimeControl::Pause))
+        .run_ticks(1)
+}
+
+fn saved_play_time(game: TestGame) -> f64 {
+    let game = game.intent(Intent::Save).run_ticks(1);
+    game.messages()
+        .iter()
+        .rev()
+        .find_map(|message| match message {
+            EngineMessage::Saved(payload) => Some(payload.decode().unwrap().play_time_seconds),
+            _ => None,
+        })
+        .expect("the game should be saved")
+}
+
+#[test]
+fn stepping_runs_the_requested_ticks_while_paused() {
+    let mut game = build_a_field_and_pause();
+    let paused = game.amount(ResourceKind::Catnip);
+
+    let mut game = game.run_ticks(5);
+    assert_eq!(game.amount(ResourceKind::Catnip), paused);
+
+    let mut game = game
+        .intent(Intent::TimeControl(TimeControl::Step(1)))
+        .run_ticks(5);
+    let per_tick = game.amount(ResourceKind::Catnip) - paused;
+    assert!(per_tick > 0.0);
+
+    // Steps run one per frame, and no more once they are done.
+    let mut game = game
+        .intent(Intent::TimeControl(TimeControl::Step(3)))
+        .run_ticks(10);
+    let stepped = game.amount(ResourceKind::Catnip) - paused;
+    assert!((stepped - 4.0 * per_tick).abs() < 1e-9);
+}
+
+#[test]
+fn stepping_does_not_add_play_time() {
+    let game = build_a_field_and_pause();
+    let paused = saved_play_time(game);
+
+    let game = build_a_field_and_pause()
+        .intent(Intent::TimeControl(TimeControl::Step(5)))
+        .run_ticks(10);
+    assert_eq!(saved_play_time(game), paused);
+}
+
+#[test]
+fn building_levels_are_sent_as_updates() {
+    let game = gather_catnip(TestGame::new(), 10)
+        .intent(BUILD_FIELD)
+        .run_ticks(1);
+    let level = game
+        .updates()
+        .filter_map(|update| match update {
+            EngineUpdate::BuildingsChanged(buildings) => {
+                *buildings.levels.get_state(&BuildingKind::CatnipField)
+            }
+            _ => None,
+        })
+        .last();
+    assert_eq!(level, Some(1));
+}
+
+#[test]
+fn gathering_and_building_matches_golden() {
+    let game = gather_catnip(TestGame::new(), 10)
+        .intent(BUILD_FIELD)
+        .run_ticks(30);
+    game.assert_golden(concat!(
+        env!("CARGO_MANIFEST_DIR"),
+        "/tests/golden/gather_and_build.txt"
+    ));
+}
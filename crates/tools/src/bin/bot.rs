@@ -0,0 +1,23 @@
+//! Plays a new game with the built-in bot and prints how many ticks each milestone took.
+//!
+//! usage: sorrow-bot [max ticks]
+
+use std::process::ExitCode;
+
+use sorrow_engine::bot;
+
+const DEFAULT_MAX_TICKS: u64 = 100_000;
+
+fn main() -> ExitCode {
+    let max_ticks = match std::env::args().nth(1).map(|arg| arg.parse()) {
+        None => DEFAULT_MAX_TICKS,
+        Some(Ok(max_ticks)) => max_ticks,
+        Some(Err(_)) => {
+            eprintln!("usage: sorrow-bot [max ticks]");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    println!("{}", bot::run(max_ticks));
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,80 @@
+//! Unpacks a diagnostic bundle downloaded from the game and prints its contents.
+//!
+//! usage: sorrow-diagnostics <bundle file>
+
+use std::process::ExitCode;
+
+use sorrow_core::diagnostics::DiagnosticBundle;
+use sorrow_engine::export;
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: sorrow-diagnostics <bundle file>");
+        return ExitCode::FAILURE;
+    };
+
+    match read(&path) {
+        Ok(bundle) => {
+            print(&bundle);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("error: could not read {path}: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn read(path: &str) -> Result<DiagnosticBundle, String> {
+    let text = std::fs::read_to_string(path).map_err(|e| e.to_string())?;
+    let json = export::unpack(&text).map_err(|e| e.to_string())?;
+    serde_json::from_str(&json).map_err(|e| e.to_string())
+}
+
+fn print(bundle: &DiagnosticBundle) {
+    println!("== build");
+    println!("version: {}", bundle.version);
+    println!("schema version: {}", bundle.schema_version);
+    println!("created at: {} ms since the epoch", bundle.created_at);
+
+    let settings = &bundle.settings;
+    println!("\n== settings");
+    println!("seconds per tick: {}", settings.seconds_per_tick);
+    println!("autosave interval: {} ticks", settings.autosave_interval);
+    println!("running state: {:?}", settings.running_state);
+    println!("speed: {}", settings.speed);
+    match &settings.active_slot {
+        Some(slot) => println!("active slot: {} ({:?})", slot.name, slot.id),
+        None => println!("active slot: none"),
+    }
+    if let Some(frame) = &settings.frame {
+        println!(
+            "last frame: {:.1}ms since the one before, {:.1}ms fed, {:.1}ms busy, {:.1}ms owed",
+            frame.gap_millis, frame.fed_millis, frame.busy_millis, frame.owed_millis
+        );
+    }
+
+    println!("\n== state");
+    match bundle.state.decode() {
+        Ok(state) => println!(
+            "{}",
+            serde_json::to_string_pretty(&state).expect("save state should always serialize")
+        ),
+        Err(error) => println!("could not decode: {error}"),
+    }
+
+    println!("\n== intents ({})", bundle.intents.len());
+    for entry in &bundle.intents {
+        println!("tick {}: {:?}", entry.tick, entry.input);
+    }
+
+    println!("\n== messages ({})", bundle.messages.len());
+    for message in &bundle.messages {
+        println!("{message}");
+    }
+
+    println!("\n== log ({})", bundle.log.len());
+    for line in &bundle.log {
+        println!("{line}");
+    }
+}
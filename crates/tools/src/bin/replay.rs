@@ -0,0 +1,59 @@
+//! Plays back a replay recorded by the game and checks that it ends in the recorded state.
+//!
+//! usage: sorrow-replay <replay file>
+
+use std::process::ExitCode;
+
+use sorrow_core::persistence::{Replay, SaveState};
+use sorrow_engine::play_back;
+
+fn main() -> ExitCode {
+    let Some(path) = std::env::args().nth(1) else {
+        eprintln!("usage: sorrow-replay <replay file>");
+        return ExitCode::FAILURE;
+    };
+
+    let replay = match std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|text| Replay::decode(&text).map_err(|e| e.to_string()))
+    {
+        Ok(replay) => replay,
+        Err(error) => {
+            eprintln!("error: could not read {path}: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let outcome = match play_back(&replay) {
+        Ok(outcome) => outcome,
+        Err(error) => {
+            eprintln!("error: {error}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if outcome.matches {
+        println!(
+            "replay of {} ticks and {} inputs matches the recording",
+            replay.ticks,
+            replay.entries.len()
+        );
+        return ExitCode::SUCCESS;
+    }
+
+    println!("replay diverged from the recording:");
+    let expected = replay.outcome.decode().map(|state| pretty(&state));
+    let actual = pretty(&outcome.state);
+    if let Ok(expected) = expected {
+        for (expected, actual) in expected.lines().zip(actual.lines()) {
+            if expected != actual {
+                println!("- {expected}\n+ {actual}");
+            }
+        }
+    }
+    ExitCode::FAILURE
+}
+
+fn pretty(state: &SaveState) -> String {
+    serde_json::to_string_pretty(state).expect("save state should always serialize")
+}
@@ -0,0 +1,86 @@
+//! Speaks the engine protocol as newline-delimited JSON over stdin and stdout, so any local process
+//! can play the game headlessly.
+//!
+//! Every input line is either an [`Intent`], such as `{"QueueWorkOrder":{"Craft":"GatherCatnip"}}`
+//! or `"Save"`, or a `{"step": N}` command. Intents are queued and handled at the start of the next
+//! step. A step runs `N` frames, each of which is exactly one tick while time is running, and then
+//! writes the [`EngineMessage`]s sent during those frames, one per line, followed by
+//! `{"Stepped":{"frame":F}}` with the number of frames run so far. Malformed lines are answered
+//! with `{"Error":"..."}`. Closing stdin exits.
+//!
+//! Save slots are kept in memory, so every session starts without any and they are gone once it
+//! exits. Use `"Export"` and `{"Import":"..."}` to carry a game between sessions.
+//!
+//! Frames where nothing changed would send empty `Updated` messages; those are left out.
+
+use std::io::{BufRead, Write};
+
+use serde::{Deserialize, Serialize};
+
+use sorrow_core::communication::{EngineMessage, Intent};
+use sorrow_engine::{storage::MemoryStorage, HeadlessEngine, HeadlessRunnerPlugin};
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum Command {
+    Step { step: u32 },
+    Intent(Intent),
+}
+
+#[derive(Serialize)]
+enum Reply {
+    Stepped { frame: u64 },
+    Error(String),
+}
+
+fn main() -> std::io::Result<()> {
+    let mut engine = HeadlessEngine::new(
+        HeadlessRunnerPlugin::one_tick_per_update(),
+        MemoryStorage::default(),
+    );
+    let mut frame = 0;
+
+    let mut out = std::io::stdout().lock();
+    write_messages(&mut out, &mut engine)?;
+    write_line(&mut out, &Reply::Stepped { frame })?;
+
+    for line in std::io::stdin().lock().lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        match serde_json::from_str::<Command>(&line) {
+            Ok(Command::Intent(intent)) => engine.send(intent),
+            Ok(Command::Step { step }) => {
+                for _ in 0..step {
+                    engine.update();
+                    frame += 1;
+                }
+                write_messages(&mut out, &mut engine)?;
+                write_line(&mut out, &Reply::Stepped { frame })?;
+            }
+            Err(error) => {
+                write_line(&mut out, &Reply::Error(format!("invalid line: {error}")))?;
+            }
+        }
+    }
+
+    engine.shutdown();
+    Ok(())
+}
+
+fn write_messages(out: &mut impl Write, engine: &mut HeadlessEngine) -> std::io::Result<()> {
+    for message in engine.receive() {
+        if !matches!(&message, EngineMessage::Updated(updates) if updates.is_empty()) {
+            write_line(out, &message)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_line(out: &mut impl Write, value: &impl Serialize) -> std::io::Result<()> {
+    serde_json::to_writer(&mut *out, value)?;
+    writeln!(out)?;
+    out.flush()
+}
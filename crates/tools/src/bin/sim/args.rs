@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+pub const USAGE: &str = "\
+usage: sorrow-sim --ticks <N> [options]
+
+options:
+    --ticks <N>          number of ticks to run
+    --plan <FILE>        JSON lines of {\"tick\": <T>, \"intent\": <Intent>}, sent after tick T
+    --load <FILE>        save to start from, either saved JSON or exported text
+    --every <N>          record every Nth tick [default: 1]
+    --format <FORMAT>    csv or json [default: from the output extension, else csv]
+    --output <FILE>      where to write the time series [default: stdout]
+    --dump <FILE>        where to write the final state as a state dump";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Csv,
+    Json,
+}
+
+#[derive(Debug)]
+pub struct Args {
+    pub ticks: u64,
+    pub plan: Option<PathBuf>,
+    pub load: Option<PathBuf>,
+    pub every: u64,
+    pub format: Format,
+    pub output: Option<PathBuf>,
+    pub dump: Option<PathBuf>,
+}
+
+impl Args {
+    pub fn parse(mut args: impl Iterator<Item = String>) -> Result<Self, String> {
+        let mut ticks = None;
+        let mut plan = None;
+        let mut load = None;
+        let mut every = 1;
+        let mut format = None;
+        let mut output = None;
+        let mut dump = None;
+
+        while let Some(arg) = args.next() {
+            let mut value = || args.next().ok_or_else(|| format!("{arg} needs a value"));
+            match arg.as_str() {
+                "--ticks" => ticks = Some(parse_count(&arg, value()?)?),
+                "--plan" => plan = Some(PathBuf::from(value()?)),
+                "--load" => load = Some(PathBuf::from(value()?)),
+                "--every" => every = parse_count(&arg, value()?)?.max(1),
+                "--format" => {
+                    format = Some(match value()?.as_str() {
+                        "csv" => Format::Csv,
+                        "json" => Format::Json,
+                        other => return Err(format!("unknown format {other}")),
+                    })
+                }
+                "--output" => output = Some(PathBuf::from(value()?)),
+                "--dump" => dump = Some(PathBuf::from(value()?)),
+                "-h" | "--help" => return Err(String::new()),
+                other => return Err(format!("unknown argument {other}")),
+            }
+        }
+
+        let format = format.unwrap_or_else(|| match &output {
+            Some(path) if path.extension().is_some_and(|ext| ext == "json") => Format::Json,
+            _ => Format::Csv,
+        });
+
+        Ok(Self {
+            ticks: ticks.ok_or("--ticks is required")?,
+            plan,
+            load,
+            every,
+            format,
+            output,
+            dump,
+        })
+    }
+}
+
+fn parse_count(arg: &str, value: String) -> Result<u64, String> {
+    value
+        .parse()
+        .map_err(|_| format!("{arg} needs a whole number, got {value}"))
+}
@@ -0,0 +1,153 @@
+//! Runs the game natively as fast as possible and records a time series of resources and
+//! buildings, for tuning balance without playing through the UI.
+
+mod args;
+mod plan;
+mod series;
+
+use std::{fs::File, io::BufWriter, path::Path, process::ExitCode};
+
+use sorrow_core::{
+    communication::{EngineMessage, Intent, TimeControl},
+    persistence::SavePayload,
+};
+use sorrow_engine::{export, storage::MemoryStorage, HeadlessEngine, HeadlessRunnerPlugin};
+
+use args::{Args, Format, USAGE};
+use plan::Plan;
+use series::Sample;
+
+fn main() -> ExitCode {
+    let args = match Args::parse(std::env::args().skip(1)) {
+        Ok(args) => args,
+        Err(error) if error.is_empty() => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("error: {error}\n\n{USAGE}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match run(&args) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run(args: &Args) -> Result<(), String> {
+    let mut plan = match &args.plan {
+        Some(path) => Plan::read(path)?,
+        None => Plan::default(),
+    };
+
+    let mut engine = HeadlessEngine::new(
+        HeadlessRunnerPlugin::one_tick_per_update(),
+        MemoryStorage::default(),
+    );
+
+    // Tick 0 is a paused frame, so the starting state is recorded before anything runs.
+    engine.send(Intent::TimeControl(TimeControl::Pause));
+    if let Some(path) = &args.load {
+        engine.send(Intent::Load(read_save(path)?));
+    }
+    engine.update();
+
+    let mut current = Sample::default();
+    let mut samples = Vec::new();
+    observe(&mut engine, &mut current)?;
+    samples.push(current.clone());
+
+    engine.send(Intent::TimeControl(TimeControl::Start));
+    for tick in 1..=args.ticks {
+        for intent in plan.take(tick - 1) {
+            engine.send(intent);
+        }
+        engine.update();
+
+        current.tick = tick;
+        observe(&mut engine, &mut current)?;
+        if tick % args.every == 0 || tick == args.ticks {
+            samples.push(current.clone());
+        }
+    }
+
+    if let Some(path) = &args.dump {
+        write_dump(&mut engine, path)?;
+    }
+
+    write(args, &samples).map_err(|error| format!("could not write the time series: {error}"))
+}
+
+/// Writes the state the run ended with as a state dump.
+fn write_dump(engine: &mut HeadlessEngine, path: &Path) -> Result<(), String> {
+    // Pausing first keeps the frame that collects the state from running another tick.
+    engine.send(Intent::TimeControl(TimeControl::Pause));
+    engine.send(Intent::Save);
+    engine.update();
+
+    let state = engine
+        .receive()
+        .into_iter()
+        .find_map(|message| match message {
+            EngineMessage::Saved(payload) => Some(payload),
+            _ => None,
+        })
+        .ok_or("the engine did not save the final state")?
+        .decode()
+        .map_err(|error| error.to_string())?;
+    let SavePayload(text) = SavePayload::encode_dump(&state);
+    std::fs::write(path, text + "\n")
+        .map_err(|error| format!("could not write {}: {error}", path.display()))
+}
+
+/// Reads a save as produced by saving or exporting the game.
+fn read_save(path: &Path) -> Result<SavePayload, String> {
+    let text = std::fs::read_to_string(path)
+        .map_err(|error| format!("could not read {}: {error}", path.display()))?;
+    let text = text.trim();
+
+    let payload = if text.starts_with('{') {
+        SavePayload(text.to_string())
+    } else {
+        export::decode(text).map_err(|error| error.to_string())?
+    };
+
+    // The run starts right where the save left off, without catching up on time since it was made.
+    let mut state = payload.decode().map_err(|error| error.to_string())?;
+    state.saved_at = None;
+    Ok(SavePayload::encode(&state))
+}
+
+fn observe(engine: &mut HeadlessEngine, sample: &mut Sample) -> Result<(), String> {
+    for message in engine.receive() {
+        match message {
+            EngineMessage::Updated(updates) => {
+                for update in &updates {
+                    sample.apply(update);
+                }
+            }
+            EngineMessage::LoadFailed(error) => {
+                return Err(format!("could not load the save: {error}"));
+            }
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+fn write(args: &Args, samples: &[Sample]) -> std::io::Result<()> {
+    let mut out: Box<dyn std::io::Write> = match &args.output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    match args.format {
+        Format::Csv => series::write_csv(samples, &mut out),
+        Format::Json => series::write_json(samples, &mut out),
+    }?;
+    out.flush()
+}
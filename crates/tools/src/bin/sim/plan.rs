@@ -0,0 +1,57 @@
+use std::path::Path;
+
+use serde::Deserialize;
+
+use sorrow_core::communication::Intent;
+
+#[derive(Deserialize, Debug)]
+struct Step {
+    tick: u64,
+    intent: Intent,
+}
+
+/// Intents to send during a run, in the order they are sent.
+#[derive(Debug, Default)]
+pub struct Plan {
+    steps: Vec<Step>,
+    next: usize,
+}
+
+impl Plan {
+    /// Reads a plan with one step per line. Blank lines and lines starting with `#` are skipped.
+    pub fn read(path: &Path) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|error| format!("could not read {}: {error}", path.display()))?;
+
+        let mut steps = Vec::new();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let step: Step = serde_json::from_str(line).map_err(|error| {
+                format!("{}:{}: invalid step: {error}", path.display(), number + 1)
+            })?;
+            steps.push(step);
+        }
+        // Steps for the same tick keep their order in the file.
+        steps.sort_by_key(|step| step.tick);
+
+        Ok(Self { steps, next: 0 })
+    }
+
+    /// Takes the intents to send once `tick` has run.
+    pub fn take(&mut self, tick: u64) -> impl Iterator<Item = Intent> + '_ {
+        let start = self.next;
+        while self
+            .steps
+            .get(self.next)
+            .is_some_and(|step| step.tick <= tick)
+        {
+            self.next += 1;
+        }
+        self.steps[start..self.next]
+            .iter()
+            .map(|step| step.intent.clone())
+    }
+}
@@ -0,0 +1,91 @@
+use std::{collections::BTreeMap, io::Write};
+
+use serde::Serialize;
+
+use sorrow_core::{
+    communication::EngineUpdate,
+    state::{buildings::BuildingKind, resources::ResourceKind, KeyIter},
+};
+
+#[derive(Serialize, Debug, Default, Clone, Copy)]
+pub struct ResourceSample {
+    pub amount: f64,
+    pub delta: f64,
+}
+
+/// State of the game after a tick, as seen through the updates the engine sent.
+#[derive(Serialize, Debug, Clone)]
+pub struct Sample {
+    pub tick: u64,
+    pub resources: BTreeMap<ResourceKind, ResourceSample>,
+    pub buildings: BTreeMap<BuildingKind, u32>,
+}
+
+impl Default for Sample {
+    fn default() -> Self {
+        Self {
+            tick: 0,
+            resources: ResourceKind::key_iter()
+                .map(|kind| (kind, ResourceSample::default()))
+                .collect(),
+            buildings: BuildingKind::key_iter().map(|kind| (kind, 0)).collect(),
+        }
+    }
+}
+
+impl Sample {
+    pub fn apply(&mut self, update: &EngineUpdate) {
+        match update {
+            EngineUpdate::ResourcesChanged(resources) => {
+                for (kind, amount) in resources.amounts.iter() {
+                    if let (Some(amount), Some(sample)) = (amount, self.resources.get_mut(kind)) {
+                        sample.amount = *amount;
+                    }
+                }
+                for (kind, delta) in resources.deltas.iter() {
+                    if let (Some(delta), Some(sample)) = (delta, self.resources.get_mut(kind)) {
+                        sample.delta = *delta;
+                    }
+                }
+            }
+            EngineUpdate::BuildingsChanged(buildings) => {
+                for (kind, level) in buildings.levels.iter() {
+                    if let Some(level) = level {
+                        self.buildings.insert(*kind, *level);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+pub fn write_csv(samples: &[Sample], out: &mut impl Write) -> std::io::Result<()> {
+    let mut header = vec!["tick".to_string()];
+    for kind in ResourceKind::key_iter() {
+        header.push(format!("{kind:?}.amount"));
+        header.push(format!("{kind:?}.delta"));
+    }
+    for kind in BuildingKind::key_iter() {
+        header.push(format!("{kind:?}.level"));
+    }
+    writeln!(out, "{}", header.join(","))?;
+
+    for sample in samples {
+        let mut row = vec![sample.tick.to_string()];
+        for resource in sample.resources.values() {
+            row.push(resource.amount.to_string());
+            row.push(resource.delta.to_string());
+        }
+        for level in sample.buildings.values() {
+            row.push(level.to_string());
+        }
+        writeln!(out, "{}", row.join(","))?;
+    }
+    Ok(())
+}
+
+pub fn write_json(samples: &[Sample], out: &mut impl Write) -> std::io::Result<()> {
+    serde_json::to_writer_pretty(&mut *out, samples)?;
+    writeln!(out)
+}
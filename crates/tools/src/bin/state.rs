@@ -0,0 +1,128 @@
+//! Prints, compares and edits game states, as saved JSON, exported text or state dumps.
+//!
+//! See [`sorrow_core::persistence::dump`] for the dump format and the field paths.
+
+use std::{collections::BTreeSet, process::ExitCode};
+
+use sorrow_core::persistence::{dump, SavePayload, SaveState};
+use sorrow_engine::export;
+
+const USAGE: &str = "\
+usage: sorrow-state <command>
+
+commands:
+    dump <FILE>                      print every field of the state
+    diff <FILE> <FILE>               print the fields that differ between two states
+    edit <FILE> [set <FIELD> <VALUE>]... [--output <FILE>]
+                                     apply the edits and write the result as a dump
+                                     [default output: stdout]
+
+Files are saved JSON, exported text or dumps. Fields are paths like resource.wood,
+resource.wood.unlocked, building.catnip_field, calendar.season or recipe.crafting.refine_catnip.";
+
+fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let result = match args.as_slice() {
+        ["dump", path] => print_dump(path),
+        ["diff", left, right] => print_diff(left, right),
+        ["edit", path, edits @ ..] => edit(path, edits),
+        ["-h" | "--help"] => {
+            println!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        _ => Err(String::new()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(error) if error.is_empty() => {
+            eprintln!("{USAGE}");
+            ExitCode::FAILURE
+        }
+        Err(error) => {
+            eprintln!("error: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Reads a state as produced by saving, exporting or dumping the game.
+fn read(path: &str) -> Result<SaveState, String> {
+    let text =
+        std::fs::read_to_string(path).map_err(|error| format!("could not read {path}: {error}"))?;
+    let text = text.trim();
+
+    let payload = if text.starts_with('{') {
+        SavePayload(text.to_string())
+    } else {
+        export::decode(text).map_err(|error| format!("could not read {path}: {error}"))?
+    };
+    payload
+        .decode()
+        .map_err(|error| format!("could not read {path}: {error}"))
+}
+
+fn print_dump(path: &str) -> Result<(), String> {
+    let state = read(path)?;
+
+    let mut section = "";
+    for (path, value) in &dump::fields(&state) {
+        let (head, rest) = path.split_once('.').unwrap_or(("", path));
+        if head != section {
+            section = head;
+            if !head.is_empty() {
+                println!("== {head}");
+            }
+        }
+        let indent = if head.is_empty() { "" } else { "    " };
+        println!("{indent}{rest}: {value}");
+    }
+    Ok(())
+}
+
+fn print_diff(left: &str, right: &str) -> Result<(), String> {
+    let left = dump::fields(&read(left)?);
+    let right = dump::fields(&read(right)?);
+
+    let paths: BTreeSet<&String> = left.keys().chain(right.keys()).collect();
+    let missing = "-".to_string();
+    for path in paths {
+        let before = left.get(path).unwrap_or(&missing);
+        let after = right.get(path).unwrap_or(&missing);
+        if before != after {
+            println!("{path}: {before} -> {after}");
+        }
+    }
+    Ok(())
+}
+
+fn edit(path: &str, edits: &[&str]) -> Result<(), String> {
+    let mut state = read(path)?;
+    let mut output = None;
+
+    let mut edits = edits.iter();
+    while let Some(arg) = edits.next() {
+        match *arg {
+            "set" => {
+                let (Some(field), Some(value)) = (edits.next(), edits.next()) else {
+                    return Err("set needs a field and a value".to_string());
+                };
+                dump::set(&mut state, field, value)?;
+            }
+            "--output" => output = Some(edits.next().ok_or("--output needs a value")?),
+            other => return Err(format!("unknown argument {other}")),
+        }
+    }
+
+    let SavePayload(text) = SavePayload::encode_dump(&state);
+    match output {
+        Some(output) => std::fs::write(output, text + "\n")
+            .map_err(|error| format!("could not write {output}: {error}")),
+        None => {
+            println!("{text}");
+            Ok(())
+        }
+    }
+}
@@ -4,14 +4,20 @@ use leptos::prelude::*;
 use reactive_stores::Store;
 use send_wrapper::SendWrapper;
 
-use sorrow_core::communication::{EngineMessage, EngineUpdate};
-use sorrow_engine::Endpoint;
+use sorrow_core::communication::{EngineMessage, EngineUpdate, Intent, ProtocolInfo};
+use sorrow_engine::{CodecError, Endpoint};
 
 use crate::store::{Global, GlobalStoreFields, IngredientFulfillmentStoreFields};
 
 pub fn connect() -> (Endpoint, Store<Global>) {
     let store = Store::new(Global::default());
-    let endpoint = Endpoint::new(move |message| update_store(store, message), "./engine.js");
+    let endpoint = Endpoint::new(
+        move |message| update_store(store, message),
+        move |error| report_codec_error(store, error),
+        "./engine.js",
+    );
+    // A cached engine can be older or newer than this page, which only a reload brings together.
+    endpoint.send(Intent::Handshake(ProtocolInfo::current()));
     (endpoint, store)
 }
 
@@ -23,25 +29,79 @@ pub fn use_endpoint() -> SendWrapper<Rc<Endpoint>> {
     expect_context::<SendWrapper<Rc<Endpoint>>>()
 }
 
+/// Tells the engine whenever the page is hidden or shown, so it can slow down while hidden.
+pub fn report_page_visibility() {
+    let endpoint = use_endpoint();
+    let send = move || endpoint.send(Intent::PageHidden(document().hidden()));
+    send();
+    window_event_listener(leptos::ev::visibilitychange, move |_| send());
+}
+
+fn report_codec_error(store: Store<Global>, error: CodecError) {
+    tracing::error!("{error}");
+    if let CodecError::Decode(_) = error {
+        // Only an engine built from other sources sends messages that can't be read.
+        store.outdated().set(true);
+    }
+}
+
 fn update_store(store: Store<Global>, message: EngineMessage) {
     match message {
-        EngineMessage::Loaded => tracing::info!("Loaded."),
+        EngineMessage::Handshake(reply) => {
+            if !reply.compatible {
+                tracing::warn!(
+                    "Engine speaks {:?}, which differs from {:?}",
+                    reply.engine,
+                    ProtocolInfo::current()
+                );
+            }
+            store.outdated().set(!reply.compatible);
+        }
+        EngineMessage::Loaded => {
+            tracing::info!("Loaded.");
+            store.load_error().set(None);
+        }
+        EngineMessage::Saved(_) => tracing::info!("Saved."),
+        EngineMessage::Exported(text) => store.exported_save().set(Some(text)),
+        EngineMessage::Replay(replay) => store.exported_replay().set(Some(replay.encode())),
+        EngineMessage::Diagnostics(text) => store.diagnostics().set(Some(text)),
+        EngineMessage::Slots(slots) => store.slots().set(slots),
+        EngineMessage::SlotOpened(slot) => {
+            store.open_slot().set(Some(slot.id));
+            store.active_slot().set(Some(slot));
+        }
+        EngineMessage::OfflineProgress(summary) => store.offline_summary().set(Some(summary)),
+        EngineMessage::LoadFailed(error) => {
+            tracing::warn!("Load failed: {error}");
+            store.load_error().set(Some(error));
+        }
         EngineMessage::Updated(updates) => {
             for update in updates {
                 accept_update(store, update);
             }
-            store.is_loaded().maybe_update(|v| {
-                if *v {
-                    false
-                } else {
-                    *v = true;
-                    true
-                }
-            });
+            mark_loaded(store);
+        }
+        EngineMessage::Snapshot(snapshot) => {
+            // Every value is filled in, so whatever the store held before is replaced.
+            for update in snapshot.into_updates() {
+                accept_update(store, update);
+            }
+            mark_loaded(store);
         }
     }
 }
 
+fn mark_loaded(store: Store<Global>) {
+    store.is_loaded().maybe_update(|v| {
+        if *v {
+            false
+        } else {
+            *v = true;
+            true
+        }
+    });
+}
+
 fn accept_update(store: Store<Global>, update: EngineUpdate) {
     match update {
         EngineUpdate::CalendarChanged(calendar) => {
@@ -125,10 +185,21 @@ fn accept_update(store: Store<Global>, update: EngineUpdate) {
                 }
             }
         }
+        EngineUpdate::PacingChanged(pacing) => {
+            if let Some(catching_up) = pacing.catching_up {
+                store.catching_up().set(catching_up);
+            }
+            if let Some(behind_seconds) = pacing.behind_seconds {
+                store.behind_seconds().set(behind_seconds);
+            }
+        }
         EngineUpdate::TimeChanged(time) => {
             if let Some(running_state) = time.running_state {
                 store.running_state().set(running_state);
             }
+            if let Some(speed) = time.speed {
+                store.speed().set(speed);
+            }
         }
         EngineUpdate::VisibilityChanged(state) => {
             use crate::store::UiStateStoreFields;
@@ -115,3 +115,13 @@ impl Roundable for f64 {
         (self * scale * (1f64 + f64::EPSILON)).trunc() / scale
     }
 }
+
+/// Formats a duration as hours and minutes, e.g. `3h 12m`.
+pub fn format_duration(seconds: f64) -> String {
+    let minutes = (seconds / 60.0).round() as u64;
+    match (minutes / 60, minutes % 60) {
+        (0, minutes) => format!("{minutes}m"),
+        (hours, 0) => format!("{hours}h"),
+        (hours, minutes) => format!("{hours}h {minutes}m"),
+    }
+}
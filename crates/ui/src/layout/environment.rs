@@ -18,12 +18,18 @@ pub fn EnvironmentContainer() -> impl IntoView {
 
     view! {
         <section class="environment-area unscroll-y flex flex-col gap-2">
-            <Calendar />
+            <div class="flex flex-row gap-2">
+                <Calendar />
+                <CatchUpIndicator />
+                <SpeedIndicator />
+            </div>
             <div>{ t!(i18n, game.blurb) }</div>
             <div class="flex flex-row gap-2 *:flex-auto">
                 <ClearLog />
+                <RewindButton />
                 <PawseButton />
             </div>
+            {cfg!(debug_assertions).then(|| view! { <DevControls /> })}
             <div class="overflow-y-hidden flex-grow flex flex-col text-sm fade-down-to-transparent space-y-4">
                 <EpochSection />
             </div>
@@ -45,6 +51,41 @@ fn Calendar() -> impl IntoView {
     }
 }
 
+#[component]
+fn CatchUpIndicator() -> impl IntoView {
+    let i18n = use_i18n();
+    let store = use_global_store();
+    let catching_up = Memo::new(move |_| store.catching_up().get());
+
+    view! {
+        <Show when=move || catching_up.get()>
+            <div
+                class="ms-auto text-sm"
+                title=move || {
+                    t_string!(i18n, game.control.behind, seconds = store.behind_seconds().get())
+                        .to_string()
+                }
+            >
+                {t!(i18n, game.control.catching_up)}
+            </div>
+        </Show>
+    }
+}
+
+#[component]
+fn SpeedIndicator() -> impl IntoView {
+    let i18n = use_i18n();
+    let speed = use_global_store().speed();
+
+    view! {
+        <Show when=move || speed.get() != 1.0>
+            <div class="ms-auto text-sm">
+                {move || t_string!(i18n, game.control.speed, speed = speed.get()).to_string()}
+            </div>
+        </Show>
+    }
+}
+
 #[component]
 fn ClearLog() -> impl IntoView {
     let i18n = use_i18n_scoped!(game.control);
@@ -56,6 +97,21 @@ fn ClearLog() -> impl IntoView {
     }
 }
 
+#[component]
+fn RewindButton() -> impl IntoView {
+    let i18n = use_i18n();
+    let endpoint = use_endpoint();
+
+    view! {
+        <button type="button"
+            class="btn padded rounded"
+            on:click=move |_| endpoint.send(Intent::Rewind { days: 1 })
+        >
+            {move || t_string!(i18n, game.control.rewind)}
+        </button>
+    }
+}
+
 #[component]
 fn PawseButton() -> impl IntoView {
     let i18n = use_i18n();
@@ -93,6 +149,49 @@ fn PawseButton() -> impl IntoView {
     }
 }
 
+/// Controls for balancing, only shown in debug builds.
+#[component]
+fn DevControls() -> impl IntoView {
+    let i18n = use_i18n();
+
+    let store = use_global_store();
+    let endpoint = use_endpoint();
+
+    let running_state = Memo::new(move |_| store.running_state().get());
+    let pawsed = Memo::new(move |_| matches!(running_state.get(), RunningState::Paused));
+    let steps = RwSignal::new(10u32);
+
+    let step_button = move |count: Signal<u32>| {
+        let endpoint = endpoint.clone();
+        view! {
+            <button type="button"
+                class="btn padded rounded"
+                prop:disabled=move || !pawsed.get()
+                on:click=move |_| endpoint.send(Intent::TimeControl(TimeControl::Step(count.get())))
+            >
+                {move || t_string!(i18n, game.control.step, count = count.get()).to_string()}
+            </button>
+        }
+    };
+
+    view! {
+        <div class="flex flex-row gap-2 items-center text-sm">
+            {step_button(Signal::stored(1))}
+            {step_button(steps.into())}
+            <input type="number"
+                class="dev-steps"
+                min="1"
+                prop:value=move || steps.get().to_string()
+                on:input=move |ev| {
+                    if let Ok(count) = event_target_value(&ev).parse::<u32>() {
+                        steps.set(count.max(1));
+                    }
+                }
+            />
+        </div>
+    }
+}
+
 #[component]
 fn EpochSection() -> impl IntoView {
     let i18n = use_i18n();
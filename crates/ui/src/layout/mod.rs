@@ -1,29 +1,44 @@
 mod controls;
 mod environment;
+mod notices;
+mod offline;
 mod resources;
+mod settings;
+mod slots;
 
 use leptos::prelude::*;
 use leptos_i18n::*;
 
-use sorrow_core::state::{
-    ui::{NavigationNodeId, NodeId},
-    KeyIter,
+use sorrow_core::{
+    communication::Intent,
+    state::{
+        ui::{NavigationNodeId, NodeId},
+        KeyIter,
+    },
 };
 
 use crate::{
+    endpoint::use_endpoint,
     i18n::use_i18n,
     store::{use_global_store, GlobalStoreFields, UiStateStoreFields},
 };
 
 use controls::ControlsContainer;
 use environment::EnvironmentContainer;
+use notices::Notices;
+use offline::OfflineSummaryDialog;
 use resources::ResourcesContainer;
+use settings::SettingsButton;
+
+pub use notices::OutdatedNotice;
+pub use slots::SlotPicker;
 
 #[component]
 pub fn App() -> impl IntoView {
     view! {
         <div id="app" class="h-full flex flex-col">
             <Header />
+            <Notices />
             <main class="flex-shrink grid-top-nav-layout gap-0 unscroll-y *:p-2">
                 <Navigation active=NavigationNodeId::Bonfire />
                 <ResourcesContainer />
@@ -31,6 +46,7 @@ pub fn App() -> impl IntoView {
                 <EnvironmentContainer />
             </main>
             <Footer />
+            <OfflineSummaryDialog />
         </div>
     }
 }
@@ -85,6 +101,12 @@ fn nav_label(
 #[component]
 fn Header() -> impl IntoView {
     let i18n = use_i18n();
+    let endpoint = use_endpoint();
+    let active_slot = use_global_store().active_slot();
+    let switch_slot = move |_| {
+        endpoint.send(Intent::ListSlots);
+        active_slot.set(None);
+    };
     view! {
         <header class="bg-gray-100/50 flex flex-row gap-1 px-2 py-1 items-center">
             <div class="order-first flex flex-row gap-1 items-center">
@@ -94,6 +116,18 @@ fn Header() -> impl IntoView {
                     " "{ t!(i18n, game.title) }" β"
                 </div>
             </div>
+            <div class="order-last ms-auto flex flex-row gap-1 items-center">
+                <span class="text-sm">
+                    {move || active_slot.with(|slot| slot.as_ref().map(|slot| slot.name.clone()))}
+                </span>
+                <button type="button"
+                    class="btn padded rounded"
+                    on:click=switch_slot
+                >
+                    {move || t_string!(i18n, persistence.slots.switch)}
+                </button>
+                <SettingsButton />
+            </div>
         </header>
     }
 }
@@ -0,0 +1,68 @@
+use leptos::prelude::*;
+use leptos_i18n::*;
+
+use sorrow_core::persistence::LoadError;
+
+use crate::{
+    i18n::use_i18n,
+    store::{use_global_store, GlobalStoreFields},
+};
+
+#[component]
+pub fn Notices() -> impl IntoView {
+    let i18n = use_i18n();
+    let load_error = use_global_store().load_error();
+
+    let message = Signal::derive(move || {
+        load_error
+            .get()
+            .map(|error| load_error_message(i18n, &error))
+    });
+
+    view! {
+        <Show when=move || message.get().is_some()>
+            <div class="bg-red-100/50 flex flex-row gap-2 px-2 py-1 items-center">
+                <div class="flex-1">{move || message.get()}</div>
+                <button type="button"
+                    class="btn padded rounded"
+                    on:click=move |_| load_error.set(None)
+                >
+                    {move || t_string!(i18n, persistence.dismiss)}
+                </button>
+            </div>
+        </Show>
+    }
+}
+
+/// Asks for a reload when the engine was built from different sources than the page.
+#[component]
+pub fn OutdatedNotice() -> impl IntoView {
+    let i18n = use_i18n();
+
+    view! {
+        <div class="w-full h-full flex flex-col gap-4 items-center justify-center">
+            <div class="text-2xl">{move || t_string!(i18n, persistence.outdated.message)}</div>
+            <button type="button"
+                class="btn padded rounded"
+                on:click=move |_| {
+                    let _ = window().location().reload();
+                }
+            >
+                {move || t_string!(i18n, persistence.outdated.reload)}
+            </button>
+        </div>
+    }
+}
+
+pub fn load_error_message(
+    i18n: leptos_i18n::I18nContext<crate::i18n::Locale>,
+    error: &LoadError,
+) -> &'static str {
+    match error {
+        LoadError::Malformed(_) => t_string!(i18n, persistence.errors.malformed),
+        LoadError::Corrupted(_) => t_string!(i18n, persistence.errors.corrupted),
+        LoadError::Storage(_) => t_string!(i18n, persistence.errors.storage),
+        LoadError::NewerVersion { .. } => t_string!(i18n, persistence.errors.newer_version),
+        LoadError::Migration { .. } => t_string!(i18n, persistence.errors.migration),
+    }
+}
@@ -0,0 +1,60 @@
+use leptos::prelude::*;
+use leptos_i18n::*;
+
+use crate::{
+    components::{numbers::DecimalView, strings::ResourceLabel},
+    formatter::{format_duration, ShowSign},
+    i18n::use_i18n,
+    store::{use_global_store, GlobalStoreFields},
+};
+
+#[component]
+pub fn OfflineSummaryDialog() -> impl IntoView {
+    let i18n = use_i18n();
+    let summary = use_global_store().offline_summary();
+
+    move || {
+        summary.get().map(|state| {
+            let duration = format_duration(state.elapsed_seconds);
+            let days = state.days;
+            let seasons = state.seasons;
+            let resources = state.resources.into_iter().collect::<Vec<_>>();
+
+            view! {
+                <div class="modal-overlay">
+                    <div class="modal-dialog">
+                        <h2 class="font-bold">{t_string!(i18n, offline.title)}</h2>
+                        <p>{t_string!(i18n, offline.elapsed, duration = duration).to_string()}</p>
+                        <ul class="resource-list">
+                            {resources
+                                .into_iter()
+                                .map(|(resource, change)| {
+                                    view! {
+                                        <li class="flex flex-row gap-2">
+                                            <DecimalView value=change show_sign=ShowSign::Always />
+                                            <ResourceLabel resource=resource />
+                                        </li>
+                                    }
+                                })
+                                .collect_view()}
+                        </ul>
+                        <Show when=move || { seasons > 0 }>
+                            <p>{t_string!(i18n, offline.seasons, count = seasons).to_string()}</p>
+                        </Show>
+                        <Show when=move || { seasons == 0 && days > 0 }>
+                            <p>{t_string!(i18n, offline.days, count = days).to_string()}</p>
+                        </Show>
+                        <div class="flex flex-row justify-end">
+                            <button type="button"
+                                class="btn padded rounded"
+                                on:click=move |_| summary.set(None)
+                            >
+                                {t_string!(i18n, offline.dismiss)}
+                            </button>
+                        </div>
+                    </div>
+                </div>
+            }
+        })
+    }
+}
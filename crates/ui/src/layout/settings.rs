@@ -0,0 +1,236 @@
+use leptos::{
+    prelude::*,
+    web_sys::{self, js_sys},
+};
+use leptos_i18n::*;
+
+use sorrow_core::{
+    communication::Intent,
+    persistence::{kittens, SavePayload},
+};
+
+use crate::{
+    endpoint::use_endpoint,
+    i18n::use_i18n,
+    store::{use_global_store, GlobalStoreFields},
+};
+
+use super::notices::load_error_message;
+
+const DIAGNOSTICS_FILE_NAME: &str = "sorrow-diagnostics.txt";
+
+#[component]
+pub fn SettingsButton() -> impl IntoView {
+    let i18n = use_i18n();
+    let is_open = RwSignal::new(false);
+
+    view! {
+        <button type="button"
+            class="btn padded rounded"
+            on:click=move |_| is_open.set(true)
+        >
+            <i class="bi bi-gear"></i>
+            " "{move || t_string!(i18n, persistence.settings.label)}
+        </button>
+        <Show when=move || is_open.get()>
+            <SettingsDialog on_close=move || is_open.set(false) />
+        </Show>
+    }
+}
+
+#[component]
+fn SettingsDialog(on_close: impl Fn() + Send + Sync + 'static) -> impl IntoView {
+    let i18n = use_i18n();
+    let endpoint = use_endpoint();
+    let store = use_global_store();
+
+    let exported_save = store.exported_save();
+    let exported_replay = store.exported_replay();
+    let diagnostics = store.diagnostics();
+    let load_error = store.load_error();
+    let import_text = RwSignal::new(String::new());
+
+    let export = {
+        let endpoint = endpoint.clone();
+        move |_| endpoint.send(Intent::Export)
+    };
+    let record_replay = {
+        let endpoint = endpoint.clone();
+        move |_| endpoint.send(Intent::ExportReplay)
+    };
+    let collect_diagnostics = {
+        let endpoint = endpoint.clone();
+        move |_| endpoint.send(Intent::ExportDiagnostics)
+    };
+    let import = {
+        let endpoint = endpoint.clone();
+        move |_| {
+            let text = import_text.get_untracked();
+            if !text.trim().is_empty() {
+                endpoint.send(Intent::Import(text));
+            }
+        }
+    };
+
+    let kittens_text = RwSignal::new(String::new());
+    let kittens_unmapped = RwSignal::new(None::<String>);
+    let import_kittens = move |_| match kittens::import(&kittens_text.get_untracked()) {
+        Ok(import) => {
+            kittens_unmapped.set((!import.unmapped.is_empty()).then(|| {
+                import
+                    .unmapped
+                    .resources
+                    .iter()
+                    .chain(import.unmapped.buildings.iter())
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            }));
+            endpoint.send(Intent::Load(SavePayload::encode(&import.state)));
+        }
+        Err(error) => {
+            kittens_unmapped.set(None);
+            load_error.set(Some(error));
+        }
+    };
+
+    let error_message = Signal::derive(move || {
+        load_error
+            .get()
+            .map(|error| load_error_message(i18n, &error))
+    });
+
+    view! {
+        <div class="modal-overlay">
+            <div class="modal-dialog">
+                <section class="flex flex-col gap-1">
+                    <div class="flex flex-row gap-2 items-center">
+                        <h2 class="flex-1 font-bold">
+                            {move || t_string!(i18n, persistence.settings.export.label)}
+                        </h2>
+                        <button type="button"
+                            class="btn padded rounded"
+                            prop:disabled=move || exported_save.with(Option::is_none)
+                            on:click=move |_| {
+                                if let Some(text) = exported_save.get() {
+                                    copy_to_clipboard(&text);
+                                }
+                            }
+                        >
+                            {move || t_string!(i18n, persistence.settings.export.copy)}
+                        </button>
+                        <button type="button" class="btn padded rounded" on:click=export>
+                            {move || t_string!(i18n, persistence.settings.export.label)}
+                        </button>
+                    </div>
+                    <p class="text-sm">{move || t_string!(i18n, persistence.settings.export.hint)}</p>
+                    <textarea
+                        class="settings-text"
+                        readonly
+                        prop:value=move || exported_save.get().unwrap_or_default()
+                        on:focus={|ev| event_target::<web_sys::HtmlTextAreaElement>(&ev).select()}
+                    ></textarea>
+                </section>
+                <section class="flex flex-col gap-1">
+                    <div class="flex flex-row gap-2 items-center">
+                        <h2 class="flex-1 font-bold">
+                            {move || t_string!(i18n, persistence.settings.import.label)}
+                        </h2>
+                        <button type="button"
+                            class="btn padded rounded"
+                            prop:disabled=move || import_text.with(|text| text.trim().is_empty())
+                            on:click=import
+                        >
+                            {move || t_string!(i18n, persistence.settings.import.label)}
+                        </button>
+                    </div>
+                    <p class="text-sm">{move || t_string!(i18n, persistence.settings.import.hint)}</p>
+                    <textarea
+                        class="settings-text"
+                        prop:value=move || import_text.get()
+                        on:input=move |ev| import_text.set(event_target_value(&ev))
+                    ></textarea>
+                    <Show when=move || error_message.get().is_some()>
+                        <p class="capped text-sm">{move || error_message.get()}</p>
+                    </Show>
+                </section>
+                <section class="flex flex-col gap-1">
+                    <div class="flex flex-row gap-2 items-center">
+                        <h2 class="flex-1 font-bold">
+                            {move || t_string!(i18n, persistence.settings.kittens.label)}
+                        </h2>
+                        <button type="button"
+                            class="btn padded rounded"
+                            prop:disabled=move || kittens_text.with(|text| text.trim().is_empty())
+                            on:click=import_kittens
+                        >
+                            {move || t_string!(i18n, persistence.settings.kittens.import)}
+                        </button>
+                    </div>
+                    <p class="text-sm">{move || t_string!(i18n, persistence.settings.kittens.hint)}</p>
+                    <textarea
+                        class="settings-text"
+                        prop:value=move || kittens_text.get()
+                        on:input=move |ev| kittens_text.set(event_target_value(&ev))
+                    ></textarea>
+                    {move || kittens_unmapped.get().map(|names| view! {
+                        <p class="text-sm">
+                            {t_string!(i18n, persistence.settings.kittens.unmapped, names = names).to_string()}
+                        </p>
+                    })}
+                </section>
+                <section class="flex flex-col gap-1">
+                    <div class="flex flex-row gap-2 items-center">
+                        <h2 class="flex-1 font-bold">
+                            {move || t_string!(i18n, persistence.settings.replay.label)}
+                        </h2>
+                        <button type="button" class="btn padded rounded" on:click=record_replay>
+                            {move || t_string!(i18n, persistence.settings.replay.record)}
+                        </button>
+                    </div>
+                    <p class="text-sm">{move || t_string!(i18n, persistence.settings.replay.hint)}</p>
+                    <textarea
+                        class="settings-text"
+                        readonly
+                        prop:value=move || exported_replay.get().unwrap_or_default()
+                        on:focus={|ev| event_target::<web_sys::HtmlTextAreaElement>(&ev).select()}
+                    ></textarea>
+                </section>
+                <section class="flex flex-col gap-1">
+                    <div class="flex flex-row gap-2 items-center">
+                        <h2 class="flex-1 font-bold">
+                            {move || t_string!(i18n, persistence.settings.diagnostics.label)}
+                        </h2>
+                        {move || diagnostics.get().map(|text| view! {
+                            <a class="btn padded rounded"
+                                download=DIAGNOSTICS_FILE_NAME
+                                href=diagnostics_url(&text)
+                            >
+                                {move || t_string!(i18n, persistence.settings.diagnostics.download)}
+                            </a>
+                        })}
+                        <button type="button" class="btn padded rounded" on:click=collect_diagnostics>
+                            {move || t_string!(i18n, persistence.settings.diagnostics.collect)}
+                        </button>
+                    </div>
+                    <p class="text-sm">{move || t_string!(i18n, persistence.settings.diagnostics.hint)}</p>
+                </section>
+                <div class="flex flex-row justify-end">
+                    <button type="button" class="btn padded rounded" on:click=move |_| on_close()>
+                        {move || t_string!(i18n, persistence.settings.close)}
+                    </button>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+fn diagnostics_url(text: &str) -> String {
+    let text: String = js_sys::encode_uri_component(text).into();
+    format!("data:text/plain;charset=utf-8,{text}")
+}
+
+fn copy_to_clipboard(text: &str) {
+    // The copy finishes in the background, and a failure leaves the text to be selected by hand.
+    let _ = window().navigator().clipboard().write_text(text);
+}
@@ -0,0 +1,169 @@
+use leptos::{prelude::*, web_sys::js_sys};
+use leptos_i18n::*;
+use wasm_bindgen::JsValue;
+
+use sorrow_core::{communication::Intent, persistence::SlotMetadata};
+
+use crate::{
+    endpoint::use_endpoint,
+    formatter::format_duration,
+    i18n::use_i18n,
+    store::{use_global_store, GlobalStoreFields},
+};
+
+use super::notices::Notices;
+
+#[component]
+pub fn SlotPicker() -> impl IntoView {
+    let i18n = use_i18n();
+    let endpoint = use_endpoint();
+    let slots = use_global_store().slots();
+
+    let new_name = RwSignal::new(String::new());
+    let create = move |_| {
+        let name = new_name.get_untracked();
+        let name = match name.trim() {
+            "" => t_string!(i18n, persistence.slots.default_name).to_string(),
+            name => name.to_string(),
+        };
+        endpoint.send(Intent::CreateSlot(name));
+    };
+
+    view! {
+        <div class="w-full h-full flex flex-col">
+            <Notices />
+            <div class="flex-1 flex flex-col items-center justify-center">
+                <div class="slot-picker">
+                    <h1 class="text-2xl font-bold">
+                        {move || t_string!(i18n, persistence.slots.title)}
+                    </h1>
+                    <Show when=move || slots.with(|slots| slots.is_empty())>
+                        <p>{move || t_string!(i18n, persistence.slots.empty)}</p>
+                    </Show>
+                    <ul class="resource-list">
+                        <For
+                            each=move || slots.get()
+                            key=|slot| (slot.id, slot.name.clone(), slot.last_played)
+                            let:slot
+                        >
+                            <SlotRow metadata=slot />
+                        </For>
+                    </ul>
+                    <div class="flex flex-row gap-2">
+                        <input type="text"
+                            class="slot-name flex-1"
+                            placeholder=move || t_string!(i18n, persistence.slots.default_name)
+                            prop:value=move || new_name.get()
+                            on:input=move |ev| new_name.set(event_target_value(&ev))
+                        />
+                        <button type="button" class="btn padded rounded" on:click=create>
+                            {move || t_string!(i18n, persistence.slots.new_game)}
+                        </button>
+                    </div>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn SlotRow(metadata: SlotMetadata) -> impl IntoView {
+    let i18n = use_i18n();
+    let endpoint = use_endpoint();
+    let open_slot = use_global_store().open_slot();
+
+    let id = metadata.id;
+    // The engine does not delete the slot that it autosaves to.
+    let is_open = move || open_slot.get() == Some(id);
+    let renaming = RwSignal::new(false);
+    let confirming_delete = RwSignal::new(false);
+    let name = RwSignal::new(metadata.name.clone());
+
+    let details = t_string!(
+        i18n,
+        persistence.slots.details,
+        year = metadata.year,
+        play_time = format_duration(metadata.play_time_seconds),
+        last_played = format_timestamp(metadata.last_played),
+    )
+    .to_string();
+    let copy_name = t_string!(
+        i18n,
+        persistence.slots.copy_name,
+        name = metadata.name.clone()
+    )
+    .to_string();
+
+    let play = {
+        let endpoint = endpoint.clone();
+        move |_| endpoint.send(Intent::OpenSlot(id))
+    };
+    let rename = {
+        let endpoint = endpoint.clone();
+        move |_| {
+            if renaming.get_untracked() {
+                let new_name = name.get_untracked();
+                if !new_name.trim().is_empty() {
+                    endpoint.send(Intent::RenameSlot(id, new_name.trim().to_string()));
+                }
+            }
+            renaming.update(|r| *r = !*r);
+        }
+    };
+    let duplicate = {
+        let endpoint = endpoint.clone();
+        move |_| endpoint.send(Intent::DuplicateSlot(id, copy_name.clone()))
+    };
+    let delete = move |_| {
+        if confirming_delete.get_untracked() {
+            endpoint.send(Intent::DeleteSlot(id));
+        } else {
+            confirming_delete.set(true);
+        }
+    };
+
+    view! {
+        <li class="flex flex-col gap-1">
+            <Show
+                when=move || renaming.get()
+                fallback=move || view! { <div class="font-bold">{move || name.get()}</div> }
+            >
+                <input type="text"
+                    class="slot-name"
+                    prop:value=move || name.get()
+                    on:input=move |ev| name.set(event_target_value(&ev))
+                />
+            </Show>
+            <div class="text-sm">{details}</div>
+            <div class="flex flex-row gap-1">
+                <button type="button" class="btn padded rounded" on:click=play>
+                    {move || t_string!(i18n, persistence.slots.play)}
+                </button>
+                <button type="button" class="btn padded rounded" on:click=rename>
+                    {move || t_string!(i18n, persistence.slots.rename)}
+                </button>
+                <button type="button" class="btn padded rounded" on:click=duplicate>
+                    {move || t_string!(i18n, persistence.slots.duplicate)}
+                </button>
+                <button type="button"
+                    class="btn padded rounded"
+                    class:capped=move || confirming_delete.get()
+                    disabled=is_open
+                    on:click=delete
+                >
+                    {move || if confirming_delete.get() {
+                        t_string!(i18n, persistence.slots.confirm_delete)
+                    } else {
+                        t_string!(i18n, persistence.slots.delete)
+                    }}
+                </button>
+            </div>
+        </li>
+    }
+}
+
+fn format_timestamp(millis: i64) -> String {
+    js_sys::Date::new(&JsValue::from_f64(millis as f64))
+        .to_locale_string("default", &JsValue::UNDEFINED)
+        .into()
+}
@@ -33,21 +33,41 @@ fn mount() {
 
         let (endpoint, store) = endpoint::connect();
         endpoint::provide_endpoint(endpoint);
+        endpoint::report_page_visibility();
         store::provide_store(store);
 
+        let is_outdated = Memo::new(move |_| store.outdated().get());
         let is_loaded = Memo::new(move |_| store.is_loaded().get());
+        let is_playing =
+            Memo::new(move |_| is_loaded.get() && store.active_slot().with(Option::is_some));
 
         view! {
             <I18nContextProvider>
                 <Conditional>
-                    <Main slot condition=is_loaded>
+                    <Main slot condition=is_outdated>
                         <Title text=move || game_title().get() />
-                        <App />
+                        <OutdatedNotice />
                     </Main>
                     <Fallback slot>
-                        <div class="w-full h-full flex flex-col items-center justify-center">
-                            <div class="text-5xl">"Loading..."</div>
-                        </div>
+                        <Conditional>
+                            <Main slot condition=is_playing>
+                                <Title text=move || game_title().get() />
+                                <App />
+                            </Main>
+                            <Fallback slot>
+                                <Conditional>
+                                    <Main slot condition=is_loaded>
+                                        <Title text=move || game_title().get() />
+                                        <SlotPicker />
+                                    </Main>
+                                    <Fallback slot>
+                                        <div class="w-full h-full flex flex-col items-center justify-center">
+                                            <div class="text-5xl">"Loading..."</div>
+                                        </div>
+                                    </Fallback>
+                                </Conditional>
+                            </Fallback>
+                        </Conditional>
                     </Fallback>
                 </Conditional>
             </I18nContextProvider>
@@ -3,6 +3,8 @@ use std::collections::BTreeMap;
 use leptos::prelude::*;
 use reactive_stores::Store;
 
+use sorrow_core::communication::OfflineSummary;
+use sorrow_core::persistence::{LoadError, SlotId, SlotMetadata};
 use sorrow_core::state::{
     buildings::BuildingKind,
     calendar::SeasonKind,
@@ -16,6 +18,7 @@ use sorrow_core::state::{
 
 #[derive(Store)]
 pub struct Building {
+    #[expect(dead_code)]
     pub building: BuildingKind,
     pub level: u32,
 }
@@ -29,6 +32,7 @@ pub struct Calendar {
 
 #[derive(Store)]
 pub struct Fulfillment {
+    #[expect(dead_code)]
     pub recipe: RecipeKind,
     pub fulfillment: FulfillmentState,
     pub ingredients: BTreeMap<ResourceKind, Store<IngredientFulfillment>>,
@@ -55,6 +59,7 @@ pub struct Resource {
 
 #[derive(Store)]
 pub struct UiState {
+    #[expect(dead_code)]
     pub node: NodeId,
     pub visible: bool,
 }
@@ -62,6 +67,17 @@ pub struct UiState {
 #[derive(Store)]
 pub struct Global {
     pub is_loaded: bool,
+    /// Whether the engine speaks a different protocol, so the page has to be reloaded.
+    pub outdated: bool,
+    pub load_error: Option<LoadError>,
+    pub exported_save: Option<String>,
+    pub exported_replay: Option<String>,
+    pub diagnostics: Option<String>,
+    pub offline_summary: Option<OfflineSummary>,
+    pub slots: Vec<SlotMetadata>,
+    pub active_slot: Option<SlotMetadata>,
+    /// Slot that the engine autosaves to, which stays open while another one is being picked.
+    pub open_slot: Option<SlotId>,
 
     pub buildings: BTreeMap<BuildingKind, Store<Building>>,
     pub calendar: Calendar,
@@ -69,6 +85,9 @@ pub struct Global {
     pub preferences: Preferences,
     pub resources: BTreeMap<ResourceKind, Store<Resource>>,
     pub running_state: RunningState,
+    pub speed: f64,
+    pub catching_up: bool,
+    pub behind_seconds: u64,
     pub ui: BTreeMap<NodeId, Store<UiState>>,
 }
 
@@ -76,6 +95,15 @@ impl Default for Global {
     fn default() -> Self {
         Self {
             is_loaded: false,
+            outdated: false,
+            load_error: None,
+            exported_save: None,
+            exported_replay: None,
+            diagnostics: None,
+            offline_summary: None,
+            slots: Vec::new(),
+            active_slot: None,
+            open_slot: None,
 
             buildings: <BuildingKind as KeyIter>::key_iter()
                 .map(|building| (building, Store::new(Building { building, level: 0 })))
@@ -127,6 +155,9 @@ impl Default for Global {
                 })
                 .collect(),
             running_state: RunningState::default(),
+            speed: 1.0,
+            catching_up: false,
+            behind_seconds: 0,
             ui: <NodeId as KeyIter>::key_iter()
                 .map(|node| {
                     (
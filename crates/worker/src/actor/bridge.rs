@@ -9,14 +9,22 @@ use serde::{Deserialize, Serialize};
 
 use super::handler_id::HandlerId;
 use super::messages::ToWorker;
-use super::native_worker::NativeWorkerExt;
+use super::native_worker::{log_codec_error, NativeWorkerExt};
 use super::traits::Worker;
 use super::{Callback, Shared};
-use crate::codec::Codec;
+use crate::codec::{Codec, CodecError};
 
 pub(crate) type ToWorkerQueue<W> = Vec<ToWorker<W>>;
 pub(crate) type CallbackMap<W> = HashMap<HandlerId, Weak<dyn Fn(<W as Worker>::Output)>>;
 
+/// Passes a codec error to the error callback, or logs it when there is none.
+pub(crate) fn report_error(error_callback: Option<&Callback<CodecError>>, error: CodecError) {
+    match error_callback {
+        Some(cb) => cb(error),
+        None => log_codec_error(&error),
+    }
+}
+
 struct WorkerBridgeInner<W>
 where
     W: Worker,
@@ -90,12 +98,17 @@ where
         pending_queue: Rc<RefCell<Option<ToWorkerQueue<W>>>>,
         callbacks: Rc<RefCell<CallbackMap<W>>>,
         callback: Option<Callback<W::Output>>,
+        error_callback: Option<Callback<CodecError>>,
     ) -> Self
     where
         CODEC: Codec,
         W::Input: Serialize + for<'de> Deserialize<'de>,
     {
-        let post_msg = move |msg: ToWorker<W>| native_worker.post_packed_message::<_, CODEC>(msg);
+        let post_msg = move |msg: ToWorker<W>| {
+            if let Err(error) = native_worker.post_packed_message::<_, CODEC>(msg) {
+                report_error(error_callback.as_ref(), error);
+            }
+        };
 
         let self_ = Self {
             inner: WorkerBridgeInner {
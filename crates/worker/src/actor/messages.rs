@@ -2,6 +2,13 @@ use serde::{Deserialize, Serialize};
 
 use super::handler_id::HandlerId;
 use super::traits::Worker;
+use crate::codec::CodecError;
+
+/// Version of how [`ToWorker`] and [`FromWorker`] frame the messages of a worker.
+///
+/// Bump this whenever they, [`HandlerId`] or [`CodecError`] change, so that a bridge refuses a
+/// worker from another build instead of misreading it.
+pub const FRAMING_VERSION: u32 = 1;
 
 /// Serializable messages to worker
 #[derive(Serialize, Deserialize, Debug)]
@@ -25,8 +32,38 @@ pub(crate) enum FromWorker<W>
 where
     W: Worker,
 {
-    /// Worker sends this message when `wasm` bundle has loaded.
-    WorkerLoaded,
+    /// Worker sends this message when `wasm` bundle has loaded, along with its
+    /// [`FRAMING_VERSION`].
+    ///
+    /// Kept as the first variant so that it is encoded the same way by every build.
+    WorkerLoaded { framing: u32 },
     /// Outgoing message to consumer
     ProcessOutput(HandlerId, W::Output),
+    /// Worker could not encode or decode a message
+    CodecError(CodecError),
+}
+
+/// Checks that a worker frames its messages the way this build does.
+pub(crate) fn check_framing(framing: u32) -> Result<(), CodecError> {
+    if framing == FRAMING_VERSION {
+        Ok(())
+    } else {
+        Err(CodecError::Decode(format!(
+            "the worker frames messages with version {framing}, but this build uses {FRAMING_VERSION}"
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn refuses_workers_that_frame_messages_differently() {
+        assert_eq!(check_framing(FRAMING_VERSION), Ok(()));
+        assert!(matches!(
+            check_framing(FRAMING_VERSION + 1),
+            Err(CodecError::Decode(_))
+        ));
+    }
 }
@@ -15,6 +15,7 @@ mod traits;
 
 pub use bridge::WorkerBridge;
 pub use handler_id::HandlerId;
+pub use messages::FRAMING_VERSION;
 pub use registrar::WorkerRegistrar;
 pub use scope::{WorkerDestroyHandle, WorkerScope};
 pub use spawner::WorkerSpawner;
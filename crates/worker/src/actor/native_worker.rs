@@ -1,7 +1,6 @@
-use crate::codec::Codec;
+use crate::codec::{Codec, CodecError};
 use serde::{Deserialize, Serialize};
 use wasm_bindgen::closure::Closure;
-use wasm_bindgen::prelude::*;
 use wasm_bindgen::{JsCast, JsValue};
 pub(crate) use web_sys::Worker as DedicatedWorker;
 use web_sys::{DedicatedWorkerGlobalScope, MessageEvent};
@@ -25,14 +24,19 @@ pub(crate) trait NativeWorkerExt {
     where
         T: Serialize + for<'de> Deserialize<'de>,
         CODEC: Codec,
-        F: 'static + Fn(T);
+        F: 'static + Fn(Result<T, CodecError>);
 
-    fn post_packed_message<T, CODEC>(&self, data: T)
+    fn post_packed_message<T, CODEC>(&self, data: T) -> Result<(), CodecError>
     where
         T: Serialize + for<'de> Deserialize<'de>,
         CODEC: Codec;
 }
 
+/// Logs a codec error to the console, for when there is nowhere else to report it.
+pub(crate) fn log_codec_error(error: &CodecError) {
+    web_sys::console::error_1(&error.to_string().into());
+}
+
 macro_rules! worker_ext_impl {
     ($($type:path),+) => {$(
         impl NativeWorkerExt for $type {
@@ -40,7 +44,7 @@ macro_rules! worker_ext_impl {
             where
                 T: Serialize + for<'de> Deserialize<'de>,
                 CODEC: Codec,
-                F: 'static + Fn(T)
+                F: 'static + Fn(Result<T, CodecError>)
             {
                 let handler = move |message: MessageEvent| {
                     let msg = CODEC::decode(message.data());
@@ -50,13 +54,13 @@ macro_rules! worker_ext_impl {
                 self.set_onmessage(Some(closure.as_ref().unchecked_ref()));
             }
 
-            fn post_packed_message<T, CODEC>(&self, data: T)
+            fn post_packed_message<T, CODEC>(&self, data: T) -> Result<(), CodecError>
             where
                 T: Serialize + for<'de> Deserialize<'de>,
                 CODEC: Codec
             {
-                self.post_message(&CODEC::encode(data))
-                    .expect_throw("failed to post message");
+                self.post_message(&CODEC::encode(data)?)
+                    .map_err(|error| CodecError::Post(format!("{error:?}")))
             }
         }
     )+};
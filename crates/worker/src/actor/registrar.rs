@@ -5,11 +5,11 @@ use serde::de::Deserialize;
 use serde::ser::Serialize;
 
 use super::lifecycle::WorkerLifecycleEvent;
-use super::messages::{FromWorker, ToWorker};
+use super::messages::{FromWorker, ToWorker, FRAMING_VERSION};
 use super::native_worker::{DedicatedWorker, NativeWorkerExt, WorkerSelf};
 use super::scope::WorkerScope;
 use super::traits::Worker;
-use crate::codec::{Bincode, Codec};
+use crate::codec::{Bincode, Codec, CodecError};
 
 /// A Worker Registrar.
 pub struct WorkerRegistrar<W, CODEC = Bincode>
@@ -66,13 +66,20 @@ where
         let scope = WorkerScope::<W>::new::<CODEC>(external_state);
         let upd = WorkerLifecycleEvent::Create(scope.clone());
         scope.send(upd);
-        let handler = move |msg: ToWorker<W>| {
-            let upd = WorkerLifecycleEvent::Remote(msg);
-            scope.send(upd);
+        let handler = {
+            let scope = scope.clone();
+            move |msg: Result<ToWorker<W>, CodecError>| match msg {
+                Ok(msg) => scope.send(WorkerLifecycleEvent::Remote(msg)),
+                Err(error) => scope.report_error(error),
+            }
+        };
+        let loaded: FromWorker<W> = FromWorker::WorkerLoaded {
+            framing: FRAMING_VERSION,
         };
-        let loaded: FromWorker<W> = FromWorker::WorkerLoaded;
         let worker = DedicatedWorker::worker_self();
         worker.set_on_packed_message::<_, CODEC, _>(handler);
-        worker.post_packed_message::<_, CODEC>(loaded);
+        if let Err(error) = worker.post_packed_message::<_, CODEC>(loaded) {
+            scope.report_error(error);
+        }
     }
 }
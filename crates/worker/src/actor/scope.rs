@@ -9,10 +9,10 @@ use wasm_bindgen_futures::spawn_local;
 use super::handler_id::HandlerId;
 use super::lifecycle::{WorkerLifecycleEvent, WorkerRunnable, WorkerState};
 use super::messages::FromWorker;
-use super::native_worker::{DedicatedWorker, NativeWorkerExt, WorkerSelf};
+use super::native_worker::{log_codec_error, DedicatedWorker, NativeWorkerExt, WorkerSelf};
 use super::traits::Worker;
 use super::Shared;
-use crate::codec::Codec;
+use crate::codec::{Codec, CodecError};
 
 /// A handle that closes the worker when it is dropped.
 pub struct WorkerDestroyHandle<W>
@@ -80,7 +80,14 @@ where
         W::Output: Serialize + for<'de> Deserialize<'de>,
     {
         let post_msg = move |msg: FromWorker<W>| {
-            DedicatedWorker::worker_self().post_packed_message::<_, CODEC>(msg)
+            let worker = DedicatedWorker::worker_self();
+            if let Err(error) = worker.post_packed_message::<_, CODEC>(msg) {
+                // The error itself is only text, so it can still be sent when the output can't.
+                let report = FromWorker::<W>::CodecError(error.clone());
+                if worker.post_packed_message::<_, CODEC>(report).is_err() {
+                    log_codec_error(&error);
+                }
+            }
         };
 
         WorkerScope {
@@ -111,6 +118,11 @@ where
         (self.post_msg)(msg);
     }
 
+    /// Reports a codec error to the bridges, which pass it to their error callback.
+    pub(crate) fn report_error(&self, error: CodecError) {
+        (self.post_msg)(FromWorker::CodecError(error));
+    }
+
     /// Send a message to the worker
     pub fn send_message<T>(&self, msg: T)
     where
@@ -10,13 +10,13 @@ use serde::ser::Serialize;
 use wasm_bindgen::UnwrapThrowExt;
 use web_sys::{Blob, BlobPropertyBag, Url};
 
-use super::bridge::{CallbackMap, WorkerBridge};
+use super::bridge::{report_error, CallbackMap, WorkerBridge};
 use super::handler_id::HandlerId;
-use super::messages::FromWorker;
+use super::messages::{check_framing, FromWorker};
 use super::native_worker::{DedicatedWorker, NativeWorkerExt};
 use super::traits::Worker;
 use super::{Callback, Shared};
-use crate::codec::{Bincode, Codec};
+use crate::codec::{Bincode, Codec, CodecError};
 
 fn window() -> web_sys::Window {
     web_sys::window().expect_throw("Can't find the global Window")
@@ -57,6 +57,7 @@ where
 {
     _marker: PhantomData<(W, CODEC)>,
     callback: Option<Callback<W::Output>>,
+    error_callback: Option<Callback<CodecError>>,
 }
 
 impl<W, CODEC> fmt::Debug for WorkerSpawner<W, CODEC>
@@ -89,6 +90,7 @@ where
         Self {
             _marker: PhantomData,
             callback: None,
+            error_callback: None,
         }
     }
 
@@ -100,6 +102,7 @@ where
         WorkerSpawner {
             _marker: PhantomData,
             callback: self.callback.clone(),
+            error_callback: self.error_callback.clone(),
         }
     }
 
@@ -113,6 +116,19 @@ where
         self
     }
 
+    /// Sets a callback for messages that could not be encoded or decoded, on either side.
+    ///
+    /// The callback is shared by every bridge forked from the spawned one. Without it, the errors
+    /// are logged to the console.
+    pub fn error_callback<F>(&mut self, cb: F) -> &mut Self
+    where
+        F: 'static + Fn(CodecError),
+    {
+        self.error_callback = Some(Rc::new(cb));
+
+        self
+    }
+
     fn spawn_inner(&self, worker: DedicatedWorker) -> WorkerBridge<W>
     where
         W::Input: Serialize + for<'de> Deserialize<'de>,
@@ -131,18 +147,26 @@ where
         let handler = {
             let pending_queue = pending_queue.clone();
             let callbacks = callbacks.clone();
+            let error_callback = self.error_callback.clone();
 
             let worker = worker.clone();
 
-            move |msg: FromWorker<W>| match msg {
-                FromWorker::WorkerLoaded => {
+            move |msg: Result<FromWorker<W>, CodecError>| match msg {
+                Ok(FromWorker::WorkerLoaded { framing }) => {
+                    if let Err(error) = check_framing(framing) {
+                        // Messages stay queued, since the worker would misread them.
+                        report_error(error_callback.as_ref(), error);
+                        return;
+                    }
                     if let Some(pending_queue) = pending_queue.borrow_mut().take() {
                         for to_worker in pending_queue.into_iter() {
-                            worker.post_packed_message::<_, CODEC>(to_worker);
+                            if let Err(error) = worker.post_packed_message::<_, CODEC>(to_worker) {
+                                report_error(error_callback.as_ref(), error);
+                            }
                         }
                     }
                 }
-                FromWorker::ProcessOutput(id, output) => {
+                Ok(FromWorker::ProcessOutput(id, output)) => {
                     let mut callbacks = callbacks.borrow_mut();
 
                     if let Some(m) = callbacks.get(&id) {
@@ -153,6 +177,9 @@ where
                         }
                     }
                 }
+                Ok(FromWorker::CodecError(error)) | Err(error) => {
+                    report_error(error_callback.as_ref(), error);
+                }
             }
         };
 
@@ -164,6 +191,7 @@ where
             pending_queue,
             callbacks,
             self.callback.clone(),
+            self.error_callback.clone(),
         )
     }
 
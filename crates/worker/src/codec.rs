@@ -1,16 +1,43 @@
+use std::fmt;
+
 use js_sys::Uint8Array;
 use serde::{Deserialize, Serialize};
-use wasm_bindgen::JsValue;
+use wasm_bindgen::{JsCast, JsValue};
+
+/// An error from encoding, decoding or posting a message.
+///
+/// It is sent across to the bridge when it happens in the worker, so it carries only text.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Eq)]
+pub enum CodecError {
+    /// The message could not be encoded.
+    Encode(String),
+    /// The message could not be decoded, usually because the other side was built differently.
+    Decode(String),
+    /// The encoded message could not be posted to the other side.
+    Post(String),
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Encode(error) => write!(f, "can't serialize a worker message: {error}"),
+            Self::Decode(error) => write!(f, "can't deserialize a worker message: {error}"),
+            Self::Post(error) => write!(f, "can't post a worker message: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for CodecError {}
 
 /// Message Encoding and Decoding Format
 pub trait Codec {
     /// Encode an input to JsValue
-    fn encode<I>(input: I) -> JsValue
+    fn encode<I>(input: I) -> Result<JsValue, CodecError>
     where
         I: Serialize;
 
     /// Decode a message to a type
-    fn decode<O>(input: JsValue) -> O
+    fn decode<O>(input: JsValue) -> Result<O, CodecError>
     where
         O: for<'de> Deserialize<'de>;
 }
@@ -20,19 +47,128 @@ pub trait Codec {
 pub struct Bincode;
 
 impl Codec for Bincode {
-    fn encode<I>(input: I) -> JsValue
+    fn encode<I>(input: I) -> Result<JsValue, CodecError>
     where
         I: Serialize,
     {
-        let buf = bincode::serialize(&input).expect("can't serialize an worker message");
-        Uint8Array::from(buf.as_slice()).into()
+        let buf = encode_bytes(&input)?;
+        Ok(Uint8Array::from(buf.as_slice()).into())
     }
 
-    fn decode<O>(input: JsValue) -> O
+    fn decode<O>(input: JsValue) -> Result<O, CodecError>
     where
         O: for<'de> Deserialize<'de>,
     {
-        let data = Uint8Array::from(input).to_vec();
-        bincode::deserialize(&data).expect("can't deserialize an worker message")
+        if !input.is_instance_of::<Uint8Array>() {
+            return Err(CodecError::Decode("expected a byte array".to_string()));
+        }
+        decode_bytes(&Uint8Array::from(input).to_vec())
+    }
+}
+
+/// Message encoding with [serde_json], which shows up as readable text in the browser's devtools.
+///
+/// Slower and larger than [`Bincode`], so meant for debugging message traffic.
+#[derive(Debug)]
+pub struct Json;
+
+impl Codec for Json {
+    fn encode<I>(input: I) -> Result<JsValue, CodecError>
+    where
+        I: Serialize,
+    {
+        Ok(JsValue::from_str(&encode_text(&input)?))
+    }
+
+    fn decode<O>(input: JsValue) -> Result<O, CodecError>
+    where
+        O: for<'de> Deserialize<'de>,
+    {
+        let text = input
+            .as_string()
+            .ok_or_else(|| CodecError::Decode("expected a string".to_string()))?;
+        decode_text(&text)
+    }
+}
+
+fn encode_bytes<I: Serialize>(input: &I) -> Result<Vec<u8>, CodecError> {
+    bincode::serialize(input).map_err(|e| CodecError::Encode(e.to_string()))
+}
+
+fn decode_bytes<O: for<'de> Deserialize<'de>>(bytes: &[u8]) -> Result<O, CodecError> {
+    bincode::deserialize(bytes).map_err(|e| CodecError::Decode(e.to_string()))
+}
+
+fn encode_text<I: Serialize>(input: &I) -> Result<String, CodecError> {
+    serde_json::to_string(input).map_err(|e| CodecError::Encode(e.to_string()))
+}
+
+fn decode_text<O: for<'de> Deserialize<'de>>(text: &str) -> Result<O, CodecError> {
+    serde_json::from_str(text).map_err(|e| CodecError::Decode(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::*;
+
+    #[derive(Serialize, Deserialize, Debug, PartialEq)]
+    enum Message {
+        Connected(usize),
+        Output { id: usize, text: String },
+    }
+
+    fn message() -> Message {
+        Message::Output {
+            id: 3,
+            text: "catnip".to_string(),
+        }
+    }
+
+    #[test]
+    fn bytes_round_trip() {
+        let bytes = encode_bytes(&message()).unwrap();
+        assert_eq!(decode_bytes::<Message>(&bytes), Ok(message()));
+    }
+
+    #[test]
+    fn text_round_trips() {
+        let text = encode_text(&message()).unwrap();
+        assert_eq!(text, r#"{"Output":{"id":3,"text":"catnip"}}"#);
+        assert_eq!(decode_text::<Message>(&text), Ok(message()));
+    }
+
+    #[test]
+    fn refuses_malformed_bytes() {
+        // A variant index past the end of the enum, and a variant cut off before its value.
+        for bytes in [&[9u8, 0, 0, 0][..], &[0, 0, 0, 0, 1], &[]] {
+            assert!(
+                matches!(decode_bytes::<Message>(bytes), Err(CodecError::Decode(_))),
+                "{bytes:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn refuses_malformed_text() {
+        for text in [
+            "{",
+            r#"{"Output":{"id":"three"}}"#,
+            r#"{"Disconnected":3}"#,
+            "",
+        ] {
+            assert!(
+                matches!(decode_text::<Message>(text), Err(CodecError::Decode(_))),
+                "{text}"
+            );
+        }
+    }
+
+    #[test]
+    fn refuses_values_it_cannot_encode() {
+        // JSON only has string keys.
+        let map = HashMap::from([((1, 2), 3)]);
+        assert!(matches!(encode_text(&map), Err(CodecError::Encode(_))));
     }
 }
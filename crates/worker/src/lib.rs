@@ -12,5 +12,5 @@ mod codec;
 mod traits;
 
 pub use actor::*;
-pub use codec::{Bincode, Codec};
+pub use codec::{Bincode, Codec, CodecError, Json};
 pub use traits::*;
@@ -0,0 +1,77 @@
+//! The codecs as they meet JavaScript values. How they encode and decode is tested natively.
+
+#![cfg(target_arch = "wasm32")]
+
+use js_sys::Uint8Array;
+use serde::{Deserialize, Serialize};
+use sorrow_worker::{Bincode, Codec, CodecError, Json};
+use wasm_bindgen::{JsCast, JsValue};
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+enum Message {
+    Connected(usize),
+    Output { id: usize, text: String },
+}
+
+fn message() -> Message {
+    Message::Output {
+        id: 3,
+        text: "catnip".to_string(),
+    }
+}
+
+fn round_trip<C: Codec>() {
+    let encoded = C::encode(message()).unwrap();
+    assert_eq!(C::decode::<Message>(encoded), Ok(message()));
+}
+
+#[wasm_bindgen_test]
+fn bincode_round_trips() {
+    round_trip::<Bincode>();
+}
+
+#[wasm_bindgen_test]
+fn json_round_trips() {
+    round_trip::<Json>();
+}
+
+#[wasm_bindgen_test]
+fn bincode_encodes_to_bytes() {
+    assert!(Bincode::encode(message())
+        .unwrap()
+        .is_instance_of::<Uint8Array>());
+}
+
+#[wasm_bindgen_test]
+fn json_encodes_to_text() {
+    let text = Json::encode(message()).unwrap().as_string().unwrap();
+    assert_eq!(text, r#"{"Output":{"id":3,"text":"catnip"}}"#);
+}
+
+#[wasm_bindgen_test]
+fn bincode_refuses_other_values() {
+    for value in [JsValue::from_str("catnip"), JsValue::from(3), JsValue::NULL] {
+        assert_eq!(
+            Bincode::decode::<Message>(value),
+            Err(CodecError::Decode("expected a byte array".to_string()))
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn json_refuses_other_values() {
+    let bytes = Uint8Array::from([0u8, 1, 2].as_slice());
+    for value in [bytes.into(), JsValue::from(3), JsValue::NULL] {
+        assert_eq!(
+            Json::decode::<Message>(value),
+            Err(CodecError::Decode("expected a string".to_string()))
+        );
+    }
+}
+
+#[wasm_bindgen_test]
+fn bincode_cannot_read_json() {
+    let encoded = Json::encode(Message::Connected(1)).unwrap();
+    assert!(Bincode::decode::<Message>(encoded).is_err());
+}